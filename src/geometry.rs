@@ -0,0 +1,102 @@
+//! Turning a [`crate::CCHQuery::node_path`] result into geographic geometry
+//! (`(lat, lon)` pairs, an encoded polyline, or a GeoJSON `LineString` feature), using the
+//! coordinates carried by [`crate::shp_utils::GraphArrays`].
+
+use crate::shp_utils::GraphArrays;
+
+/// Route geometry built from a CCH node path plus the coordinates it was queried against.
+///
+/// `distance_m` and `eta_seconds` are optional annotations the caller fills in from the query
+/// result (e.g. `CCHQuery::distance()` as raw meters, or as travel-time milliseconds / 1000 when
+/// the metric came from [`crate::shp_utils::WeightProfile`]); `PathGeometry` itself only knows
+/// about coordinates.
+pub struct PathGeometry {
+    /// `(lat, lon)` per node along the path, in traversal order.
+    pub coords: Vec<(f64, f64)>,
+    pub distance_m: Option<f64>,
+    pub eta_seconds: Option<f64>,
+}
+
+impl PathGeometry {
+    /// Build from a `GraphArrays` (for the `xs`/`ys` coordinate lookup) and a node path as
+    /// returned by [`crate::CCHQuery::node_path`].
+    ///
+    /// Panics if any node id in `node_path` is out of range for `graph`.
+    pub fn new(graph: &GraphArrays, node_path: &[u32]) -> Self {
+        let coords = node_path
+            .iter()
+            .map(|&n| (graph.ys[n as usize], graph.xs[n as usize]))
+            .collect();
+        PathGeometry {
+            coords,
+            distance_m: None,
+            eta_seconds: None,
+        }
+    }
+
+    pub fn with_distance_m(mut self, distance_m: f64) -> Self {
+        self.distance_m = Some(distance_m);
+        self
+    }
+
+    pub fn with_eta_seconds(mut self, eta_seconds: f64) -> Self {
+        self.eta_seconds = Some(eta_seconds);
+        self
+    }
+
+    /// Encode as a Google-style polyline string (precision `1e5`, the Google Maps default).
+    pub fn to_polyline(&self) -> String {
+        encode_polyline(&self.coords, 1e5)
+    }
+
+    /// Encode as a GeoJSON `Feature` wrapping a `LineString`, `[lon, lat]` per point per the
+    /// GeoJSON spec. `distance_m`/`eta_seconds` (when set) are added as feature properties.
+    pub fn to_geojson(&self) -> serde_json::Value {
+        let coordinates: Vec<[f64; 2]> = self.coords.iter().map(|&(lat, lon)| [lon, lat]).collect();
+        let mut properties = serde_json::Map::new();
+        if let Some(d) = self.distance_m {
+            properties.insert("distance_m".to_string(), serde_json::json!(d));
+        }
+        if let Some(eta) = self.eta_seconds {
+            properties.insert("eta_seconds".to_string(), serde_json::json!(eta));
+        }
+        serde_json::json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "LineString",
+                "coordinates": coordinates,
+            },
+            "properties": serde_json::Value::Object(properties),
+        })
+    }
+}
+
+/// Google polyline algorithm: delta-encode scaled, rounded coordinates as a sequence of
+/// variable-length base64-ish ASCII characters.
+fn encode_polyline(coords: &[(f64, f64)], precision: f64) -> String {
+    fn encode_value(mut value: i64) -> String {
+        value <<= 1;
+        if value < 0 {
+            value = !value;
+        }
+        let mut out = String::new();
+        while value >= 0x20 {
+            out.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+            value >>= 5;
+        }
+        out.push((value as u8 + 63) as char);
+        out
+    }
+
+    let mut out = String::new();
+    let (mut prev_lat, mut prev_lon) = (0i64, 0i64);
+    for &(lat, lon) in coords {
+        let lat_i = (lat * precision).round() as i64;
+        let lon_i = (lon * precision).round() as i64;
+        out.push_str(&encode_value(lat_i - prev_lat));
+        out.push_str(&encode_value(lon_i - prev_lon));
+        prev_lat = lat_i;
+        prev_lon = lon_i;
+    }
+    out
+}
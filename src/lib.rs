@@ -1,7 +1,16 @@
 #![doc = include_str!("../README.md")]
 
 // Expose test support utilities
+pub mod geometry;
+pub mod query_pool;
 pub mod shp_utils;
+pub mod turn_expanded_graph;
+pub mod waypoint_order;
+
+pub use geometry::PathGeometry;
+pub use query_pool::CCHQueryPool;
+pub use turn_expanded_graph::TurnExpandedGraph;
+pub use waypoint_order::solve_waypoint_order;
 
 #[cxx::bridge]
 mod ffi {
@@ -12,6 +21,9 @@ mod ffi {
         type CCHMetric; // CustomizableContractionHierarchyMetric
         type CCHQuery; // CustomizableContractionHierarchyQuery
         type CCHPartial; // CustomizableContractionHierarchyPartialCustomization
+        type CCHMatrix; // many-to-many distance table engine: one independent query per (source, target) pair, not bucket-amortized
+        type CCHManyToMany; // incremental many-to-many engine: sources/targets can be reselected, but still one independent query per pair
+        type CCHRestrictedGraph; // cached target set for repeated one-to-many queries; not RoutingKit's native RPHAST sweep
 
         /// Build a Customizable Contraction Hierarchy.
         /// Arguments:
@@ -72,6 +84,84 @@ mod ffi {
         /// Extract the arc (edge) path corresponding to the shortest path.
         /// Each entry is an original arc id (after shortcut unpacking).
         unsafe fn cch_query_arc_path(query: &CCHQuery) -> Vec<u32>;
+
+        /// Allocate a many-to-many distance table engine bound to a metric.
+        unsafe fn cch_matrix_new(metric: &CCHMetric) -> UniquePtr<CCHMatrix>;
+
+        /// Compute the full `sources.len() * targets.len()` distance matrix: one independent
+        /// `CustomizableContractionHierarchyQuery` per (source, target) pair. Correct, but this is
+        /// not the amortized bucket-scatter/merge a native many-to-many engine would run —
+        /// RoutingKit's public `CustomizableContractionHierarchyQuery` API doesn't expose the
+        /// per-node upward/downward shortcut arrays a bucket merge needs, so that algorithm isn't
+        /// implementable against this library without reaching into its internals; see the doc
+        /// comment on the outer `CCHMatrix` wrapper in this file.
+        /// Returns a row-major flat buffer (`row = source index`, `col = target index`);
+        /// unreachable pairs are `u32::MAX`.
+        unsafe fn cch_matrix_compute(
+            matrix: Pin<&mut CCHMatrix>,
+            sources: &[u32],
+            targets: &[u32],
+        ) -> Vec<u32>;
+
+        /// Serialize the inputs a `CCH` was built from (order, tail, head,
+        /// `filter_always_inf_arcs`) to an opaque byte buffer. RoutingKit does not expose a way
+        /// to dump its internal elimination-tree/shortcut arrays directly, so `cch_deserialize`
+        /// rebuilds from these cached inputs instead (nested dissection is skipped since `order`
+        /// is reused as-is, but contraction still reruns).
+        unsafe fn cch_serialize(cch: &CCH) -> Vec<u8>;
+
+        /// Rebuild a `CCH` from bytes previously produced by `cch_serialize`, skipping nested
+        /// dissection entirely. Returns `Err` (rather than panicking/aborting) if `bytes` is
+        /// truncated or otherwise malformed, so a corrupt cache file surfaces as a catchable
+        /// error instead of tearing down the process.
+        unsafe fn cch_deserialize(bytes: &[u8]) -> Result<UniquePtr<CCH>>;
+
+        /// Serialize a customized metric's raw per-arc weights to an opaque byte buffer.
+        /// `cch_metric_deserialize` re-derives the upward/downward shortcut arrays from this by
+        /// re-running customization; RoutingKit does not expose a way to dump and restore those
+        /// shortcut arrays directly, so there is no cheaper path today.
+        unsafe fn cch_metric_serialize(metric: &CCHMetric) -> Vec<u8>;
+
+        /// Rebuild a customized `CCHMetric` for `cch` from bytes previously produced by
+        /// `cch_metric_serialize`. Re-runs `cch_metric_customize` internally from the cached
+        /// weights (see `cch_metric_serialize`); this cache exists so the caller doesn't have to
+        /// keep its own copy of the weight vector around, not to skip customization itself.
+        /// Returns `Err` (rather than panicking/aborting) if `bytes` is truncated or otherwise
+        /// malformed, so a corrupt cache file surfaces as a catchable error instead of tearing
+        /// down the process.
+        unsafe fn cch_metric_deserialize(cch: &CCH, bytes: &[u8]) -> Result<UniquePtr<CCHMetric>>;
+
+        /// Allocate an incremental many-to-many engine bound to a metric. Unlike `CCHMatrix`,
+        /// sources and targets are selected (and reselected) independently of `run`, so a fixed
+        /// target set can be reused across many different source batches.
+        unsafe fn cch_many_to_many_new(metric: &CCHMetric) -> UniquePtr<CCHManyToMany>;
+
+        /// Cache the source set for subsequent `cch_many_to_many_run` calls, replacing any
+        /// previous selection.
+        unsafe fn cch_many_to_many_select_sources(engine: Pin<&mut CCHManyToMany>, sources: &[u32]);
+
+        /// Cache the target set for subsequent `cch_many_to_many_run` calls, replacing any
+        /// previous selection.
+        unsafe fn cch_many_to_many_select_targets(engine: Pin<&mut CCHManyToMany>, targets: &[u32]);
+
+        /// Compute the row-major `sources.len() * targets.len()` distance matrix for the
+        /// currently selected sources/targets via one independent query per pair (`u32::MAX` for
+        /// unreachable). Must be called after at least one `select_sources`/`select_targets` pair.
+        unsafe fn cch_many_to_many_run(engine: Pin<&mut CCHManyToMany>) -> Vec<u32>;
+
+        /// Cache `targets` on a restricted-graph handle so each `cch_restricted_graph_query` call
+        /// only has to supply a source. Immutable once built, so it may be queried from many
+        /// threads at once.
+        unsafe fn cch_restricted_graph_new(
+            metric: &CCHMetric,
+            targets: &[u32],
+        ) -> UniquePtr<CCHRestrictedGraph>;
+
+        /// Distance from `source` to every cached target, via one independent query per target,
+        /// in the order `targets` was passed to `cch_restricted_graph_new` (`u32::MAX` for
+        /// unreachable). Not RoutingKit's rank-sorted restricted-arc sweep; see the doc comment on
+        /// the outer `CCHQuery::compute_matrix` in this file.
+        unsafe fn cch_restricted_graph_query(graph: &CCHRestrictedGraph, source: u32) -> Vec<u32>;
     }
 
     unsafe extern "C++" {
@@ -111,6 +201,18 @@ unsafe impl Send for ffi::CCHMetric {}
 unsafe impl Sync for ffi::CCHMetric {}
 unsafe impl Send for ffi::CCHQuery {}
 // (No Sync for CCHQuery)
+unsafe impl Send for ffi::CCHMatrix {}
+// (No Sync for CCHMatrix: cch_matrix_compute takes it by mutable reference, same as CCHQuery)
+unsafe impl Send for ffi::CCHManyToMany {}
+// (No Sync for CCHManyToMany: select_sources/select_targets/run all mutate the cached source/
+// target selection)
+unsafe impl Send for ffi::CCHPartial {}
+// (No Sync for CCHPartial: reset/update_arc/customize all mutate internal scratch state, same
+// rationale as CCHQuery/CCHMatrix.)
+unsafe impl Send for ffi::CCHRestrictedGraph {}
+unsafe impl Sync for ffi::CCHRestrictedGraph {}
+// (CCHRestrictedGraph is Sync: immutable once built by cch_restricted_graph_new, and each
+// cch_restricted_graph_query call uses only its own local scratch, same rationale as CCH/CCHMetric.)
 
 // Rust wrapper over FFI
 use cxx::UniquePtr;
@@ -123,6 +225,10 @@ pub use ffi::{
 
 pub struct CCH {
     inner: UniquePtr<ffi::CCH>,
+    order: Box<[u32]>,
+    tail: Box<[u32]>,
+    head: Box<[u32]>,
+    filter_always_inf_arcs: bool,
 }
 
 impl CCH {
@@ -146,10 +252,231 @@ impl CCH {
     /// Panics: never (undefined behavior if input slices have inconsistent lengths – guarded by `cxx`).
     pub fn new(order: &[u32], tail: &[u32], head: &[u32], filter_always_inf_arcs: bool) -> Self {
         let cch = unsafe { cch_new(order, tail, head, filter_always_inf_arcs) };
-        CCH { inner: cch }
+        CCH {
+            inner: cch,
+            order: order.into(),
+            tail: tail.into(),
+            head: head.into(),
+            filter_always_inf_arcs,
+        }
+    }
+
+    /// Content fingerprint (SHA3-256) over `order`, `tail`, `head`, and
+    /// `filter_always_inf_arcs` — the exact inputs nested dissection and contraction depend on.
+    /// Two graphs that hash the same are guaranteed to contract into the same hierarchy.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        Self::fingerprint_of(
+            &self.order,
+            &self.tail,
+            &self.head,
+            self.filter_always_inf_arcs,
+        )
+    }
+
+    /// Build from a previously computed order, validating it's a genuine permutation of
+    /// `0..order.len()` rather than trusting the caller (an invalid order is undefined behavior
+    /// once it reaches `cch_new`'s C++ side).
+    ///
+    /// Use this to skip nested dissection on repeat builds of the same graph: compute the order
+    /// once with [`compute_order_inertial`] (or an external partitioner), store it alongside the
+    /// graph, then feed it back here instead of recomputing it.
+    pub fn from_order(
+        order: &[u32],
+        tail: &[u32],
+        head: &[u32],
+        filter_always_inf_arcs: bool,
+    ) -> Result<Self, InvalidOrderError> {
+        let node_count = order.len();
+        let mut seen = vec![false; node_count];
+        for &n in order {
+            let idx = n as usize;
+            if idx >= node_count {
+                return Err(InvalidOrderError::OutOfRange(n));
+            }
+            if std::mem::replace(&mut seen[idx], true) {
+                return Err(InvalidOrderError::Duplicate(n));
+            }
+        }
+        Ok(Self::new(order, tail, head, filter_always_inf_arcs))
+    }
+
+    /// Same as [`CCH::new`], but reports a [`Progress`] event before and after contraction.
+    ///
+    /// Useful for surfacing a long-running build behind a progress indicator; see [`Progress`]
+    /// for why these are phase boundaries rather than fine-grained steps.
+    pub fn new_with_progress(
+        order: &[u32],
+        tail: &[u32],
+        head: &[u32],
+        filter_always_inf_arcs: bool,
+        mut progress: impl FnMut(Progress),
+    ) -> Self {
+        progress(Progress {
+            phase: "contract",
+            fraction: 0.0,
+        });
+        let cch = Self::new(order, tail, head, filter_always_inf_arcs);
+        progress(Progress {
+            phase: "contract",
+            fraction: 1.0,
+        });
+        cch
+    }
+
+    /// The order permutation this `CCH` was built with — read it back to persist it or to feed
+    /// [`CCH::from_order`] on a later build of the same graph.
+    pub fn order(&self) -> &[u32] {
+        &self.order
+    }
+
+    fn fingerprint_of(
+        order: &[u32],
+        tail: &[u32],
+        head: &[u32],
+        filter_always_inf_arcs: bool,
+    ) -> [u8; 32] {
+        use sha3::{Digest, Sha3_256};
+        let mut hasher = Sha3_256::new();
+        for slice in [order, tail, head] {
+            for v in slice {
+                hasher.update(v.to_le_bytes());
+            }
+        }
+        hasher.update([filter_always_inf_arcs as u8]);
+        hasher.finalize().into()
+    }
+
+    /// Persist the inputs this `CCH` was built from (`order`/`tail`/`head`/
+    /// `filter_always_inf_arcs`) to `path`, prefixed by a header carrying [`CCH::fingerprint`] so a
+    /// mismatched cache is never silently reused.
+    ///
+    /// The metric/weights are *not* included: they are separable from the topology and still
+    /// need a fresh [`CCHMetric::new`]/[`CCHMetric::parallel_new`] customization on load.
+    ///
+    /// Performance: this only saves recomputing `order` (nested dissection) on the next load —
+    /// see [`CCH::load`]. Contraction itself is not cached (RoutingKit doesn't expose a way to
+    /// dump and restore its internal shortcut arrays) and reruns every time regardless of whether
+    /// `order` came from a fresh nested-dissection run or from this cache. For graphs where
+    /// contraction, not ordering, dominates build time, this save/load pair buys little.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let payload = unsafe { cch_serialize(&self.inner) };
+        let mut file = std::fs::File::create(path)?;
+        use std::io::Write;
+        file.write_all(CCH_CACHE_MAGIC)?;
+        file.write_all(&self.fingerprint())?;
+        file.write_all(&payload)?;
+        Ok(())
+    }
+
+    /// Load a `CCH` previously written by [`CCH::save`], skipping nested dissection entirely
+    /// (contraction still reruns from the cached order — RoutingKit doesn't expose a way to
+    /// restore its internal shortcut arrays directly).
+    ///
+    /// `order`/`tail`/`head`/`filter_always_inf_arcs` must be the exact inputs the cache was
+    /// built from; their fingerprint is recomputed and compared against the file header so stale
+    /// topology (a changed map, a changed order) can never produce silently wrong routes.
+    pub fn load<P: AsRef<std::path::Path>>(
+        path: P,
+        order: &[u32],
+        tail: &[u32],
+        head: &[u32],
+        filter_always_inf_arcs: bool,
+    ) -> Result<Self, CchCacheError> {
+        let bytes = std::fs::read(path).map_err(CchCacheError::Io)?;
+        let header_len = CCH_CACHE_MAGIC.len() + 32;
+        if bytes.len() < header_len || &bytes[..CCH_CACHE_MAGIC.len()] != CCH_CACHE_MAGIC {
+            return Err(CchCacheError::Corrupt("missing or invalid magic header"));
+        }
+        let stored_fingerprint = &bytes[CCH_CACHE_MAGIC.len()..header_len];
+        let expected_fingerprint = Self::fingerprint_of(order, tail, head, filter_always_inf_arcs);
+        if stored_fingerprint != expected_fingerprint {
+            return Err(CchCacheError::FingerprintMismatch);
+        }
+        let payload = &bytes[header_len..];
+        let inner = unsafe { cch_deserialize(payload) }
+            .map_err(|_| CchCacheError::Corrupt("truncated or malformed cch cache payload"))?;
+        Ok(CCH {
+            inner,
+            order: order.into(),
+            tail: tail.into(),
+            head: head.into(),
+            filter_always_inf_arcs,
+        })
+    }
+}
+
+const CCH_CACHE_MAGIC: &[u8; 4] = b"CCH1";
+
+/// A coarse-grained progress event reported by the `_with_progress` variants of [`CCH::new`],
+/// [`CCHMetric::new`], and [`CCHMetricPartialUpdater::apply`].
+///
+/// The underlying RoutingKit routines run as a single opaque call with no hooks for per-node
+/// progress, so `phase` names the step being timed (e.g. `"contract"`, `"customize"`) and
+/// `fraction` is only ever `0.0` (about to start) or `1.0` (just finished) — not a smooth sweep.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    pub phase: &'static str,
+    pub fraction: f32,
+}
+
+/// Error returned by [`CCH::from_order`] when the supplied slice isn't a genuine permutation of
+/// `0..order.len()`.
+#[derive(Debug)]
+pub enum InvalidOrderError {
+    /// The same node id appeared more than once in `order`.
+    Duplicate(u32),
+    /// A node id in `order` fell outside `0..order.len()`.
+    OutOfRange(u32),
+}
+
+impl std::fmt::Display for InvalidOrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvalidOrderError::Duplicate(n) => {
+                write!(f, "node {n} appears more than once in the order")
+            }
+            InvalidOrderError::OutOfRange(n) => {
+                write!(f, "node {n} is out of range for the order's length")
+            }
+        }
     }
 }
 
+impl std::error::Error for InvalidOrderError {}
+
+/// Error returned by [`CCH::load`] and [`CCHMetric::load`].
+#[derive(Debug)]
+pub enum CchCacheError {
+    /// Reading the cache file failed.
+    Io(std::io::Error),
+    /// The file is too short, doesn't start with the expected magic bytes, or its payload is
+    /// truncated or malformed.
+    Corrupt(&'static str),
+    /// The cache's embedded fingerprint doesn't match the supplied/owning `CCH` — the cache is
+    /// for a different graph and must not be reused.
+    FingerprintMismatch,
+    /// A metric cache's arc count doesn't match the `CCH` it's being bound to.
+    ArcCountMismatch { expected: usize, found: usize },
+}
+
+impl std::fmt::Display for CchCacheError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CchCacheError::Io(e) => write!(f, "failed to read CCH cache: {e}"),
+            CchCacheError::Corrupt(msg) => write!(f, "corrupt CCH cache: {msg}"),
+            CchCacheError::FingerprintMismatch => {
+                write!(f, "CCH cache fingerprint does not match the supplied graph")
+            }
+            CchCacheError::ArcCountMismatch { expected, found } => write!(
+                f,
+                "CCH metric cache has {found} weights, but the CCH has {expected} arcs"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CchCacheError {}
+
 pub struct CCHMetric<'a> {
     inner: UniquePtr<ffi::CCHMetric>,
     weights: Box<[u32]>, // owned stable backing storage (no reallocation)
@@ -176,6 +503,26 @@ impl<'a> CCHMetric<'a> {
         }
     }
 
+    /// Same as [`CCHMetric::new`], but reports a [`Progress`] event before and after
+    /// customization. See [`Progress`] for why these are phase boundaries rather than
+    /// fine-grained steps.
+    pub fn new_with_progress(
+        cch: &'a CCH,
+        weights: Vec<u32>,
+        mut progress: impl FnMut(Progress),
+    ) -> Self {
+        progress(Progress {
+            phase: "customize",
+            fraction: 0.0,
+        });
+        let metric = Self::new(cch, weights);
+        progress(Progress {
+            phase: "customize",
+            fraction: 1.0,
+        });
+        metric
+    }
+
     /// Parallel customization variant.
     pub fn parallel_new(cch: &'a CCH, weights: Vec<u32>, thread_count: u32) -> Self {
         let boxed: Box<[u32]> = weights.into_boxed_slice();
@@ -195,8 +542,75 @@ impl<'a> CCHMetric<'a> {
     pub fn weights(&self) -> &[u32] {
         &self.weights
     }
+
+    /// Persist the customized metric's raw per-arc weights to `path`, prefixed by a header
+    /// carrying the owning [`CCH::fingerprint`] so `load` can refuse to bind the cache to a
+    /// different hierarchy.
+    ///
+    /// Performance: this caches the weight vector only, not the customized upward/downward
+    /// shortcut arrays customization produces (RoutingKit doesn't expose a way to dump and restore
+    /// those directly) — [`CCHMetric::load`] re-runs `customize()` from the cached weights every
+    /// time. This save/load pair saves the caller from keeping its own copy of the weight vector
+    /// around; it does not skip the customization step itself.
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        let payload = unsafe { cch_metric_serialize(&self.inner) };
+        let mut file = std::fs::File::create(path)?;
+        use std::io::Write;
+        file.write_all(CCH_METRIC_CACHE_MAGIC)?;
+        file.write_all(&self.cch.fingerprint())?;
+        file.write_all(&payload)?;
+        Ok(())
+    }
+
+    /// Load a metric previously written by [`CCHMetric::save`], bound to `cch`.
+    ///
+    /// Refuses to load if the cache's embedded fingerprint doesn't match `cch.fingerprint()`
+    /// (the cache is for a different hierarchy) or if the cached arc count doesn't match `cch`'s
+    /// arc count. Re-establishes the owned `Box<[u32]>` weight backing store, so the result
+    /// remains valid for [`CCHMetricPartialUpdater::apply`].
+    pub fn load<P: AsRef<std::path::Path>>(cch: &'a CCH, path: P) -> Result<Self, CchCacheError> {
+        let bytes = std::fs::read(path).map_err(CchCacheError::Io)?;
+        let header_len = CCH_METRIC_CACHE_MAGIC.len() + 32;
+        if bytes.len() < header_len
+            || &bytes[..CCH_METRIC_CACHE_MAGIC.len()] != CCH_METRIC_CACHE_MAGIC
+        {
+            return Err(CchCacheError::Corrupt("missing or invalid magic header"));
+        }
+        let stored_fingerprint = &bytes[CCH_METRIC_CACHE_MAGIC.len()..header_len];
+        if stored_fingerprint != cch.fingerprint() {
+            return Err(CchCacheError::FingerprintMismatch);
+        }
+        let payload = &bytes[header_len..];
+        if payload.len() < 4 {
+            return Err(CchCacheError::Corrupt("truncated metric cache payload"));
+        }
+        let weights_len = u32::from_le_bytes(payload[..4].try_into().unwrap()) as usize;
+        if weights_len != cch.tail.len() {
+            return Err(CchCacheError::ArcCountMismatch {
+                expected: cch.tail.len(),
+                found: weights_len,
+            });
+        }
+        let weights_bytes_len = weights_len * 4;
+        if payload.len() < 4 + weights_bytes_len {
+            return Err(CchCacheError::Corrupt("truncated weight array"));
+        }
+        let weights: Box<[u32]> = payload[4..4 + weights_bytes_len]
+            .chunks_exact(4)
+            .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        let inner = unsafe { cch_metric_deserialize(&cch.inner, payload) }
+            .map_err(|_| CchCacheError::Corrupt("truncated or malformed metric cache payload"))?;
+        Ok(CCHMetric {
+            inner,
+            weights,
+            cch,
+        })
+    }
 }
 
+const CCH_METRIC_CACHE_MAGIC: &[u8; 4] = b"CMET";
+
 /// Reusable partial customization helper. Construct once if you perform many small incremental
 /// weight updates; this avoids reallocating O(m) internal buffers each call.
 pub struct CCHMetricPartialUpdater<'a> {
@@ -241,6 +655,29 @@ impl<'a> CCHMetricPartialUpdater<'a> {
             );
         }
     }
+
+    /// Same as [`CCHMetricPartialUpdater::apply`], but reports a [`Progress`] event before and
+    /// after the partial customization. See [`Progress`] for why these are phase boundaries
+    /// rather than fine-grained steps.
+    pub fn apply_with_progress<T>(
+        &mut self,
+        metric: &mut CCHMetric<'a>,
+        updates: &T,
+        mut progress: impl FnMut(Progress),
+    ) where
+        T: for<'b> std::ops::Index<&'b u32, Output = u32>,
+        for<'b> &'b T: IntoIterator<Item = (&'b u32, &'b u32)>,
+    {
+        progress(Progress {
+            phase: "partial_customize",
+            fraction: 0.0,
+        });
+        self.apply(metric, updates);
+        progress(Progress {
+            phase: "partial_customize",
+            fraction: 1.0,
+        });
+    }
 }
 
 pub struct CCHQuery<'a> {
@@ -350,4 +787,160 @@ impl<'a> CCHQuery<'a> {
         }
         unsafe { cch_query_arc_path(self.inner.as_ref().unwrap()) }
     }
+
+    /// One-to-many convenience over [`CCHMatrix`]: distances from `source` to every node in
+    /// `targets`, in the same order as `targets`. `u32::MAX` marks an unreachable target.
+    ///
+    /// Prefer [`CCHQuery::compute_matrix`] directly when you have more than one source, so the
+    /// target set is cached once across the whole batch instead of once per source.
+    pub fn phast_to_targets(&self, source: u32, targets: &[u32]) -> Vec<u32> {
+        let mut matrix = CCHMatrix::new(self.metric);
+        matrix.compute(&[source], targets)
+    }
+
+    /// Full `sources.len() x targets.len()` distance table, computed in parallel across
+    /// `sources`. Row `i` holds the distances from `sources[i]` to every target, in `targets`
+    /// order; `u32::MAX` marks an unreachable pair.
+    ///
+    /// Caches `targets` once (rather than once per source), then shards `sources` across a
+    /// bounded pool of worker threads (one per available core), since the cached target handle is
+    /// read-only after it's built and each source's queries only touch their own scratch. Prefer
+    /// this over independent [`CCHQuery::phast_to_targets`] calls when `targets` is reused across
+    /// many sources, since `targets` is then cached once instead of once per source.
+    ///
+    /// Each row is still one independent shortest-path query per target; there is no
+    /// rank-sorted sweep over a restricted set of downward arcs (RPHAST).
+    pub fn compute_matrix(&self, sources: &[u32], targets: &[u32]) -> Vec<Vec<u32>> {
+        let graph = unsafe { cch_restricted_graph_new(&self.metric.inner, targets) };
+        if sources.is_empty() {
+            return Vec::new();
+        }
+        let thread_count = std::thread::available_parallelism()
+            .map(std::num::NonZeroUsize::get)
+            .unwrap_or(1)
+            .min(sources.len());
+        let chunk_size = sources.len().div_ceil(thread_count);
+        std::thread::scope(|scope| {
+            sources
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let graph = &graph;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|&source| unsafe {
+                                cch_restricted_graph_query(graph.as_ref().unwrap(), source)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+}
+
+/// Many-to-many distance table over a customized [`CCHMetric`].
+///
+/// Internally this runs one independent [`CCHQuery`] per (source, target) pair — correct, but it
+/// does not amortize upward/downward sweeps across rows the way RoutingKit's native bucket-based
+/// many-to-many engine would. That amortized engine is not something this crate can add on top of
+/// RoutingKit later, either: RoutingKit's public API exposes a per-pair query object, not the
+/// per-node shortcut arrays a bucket scatter/merge needs to read directly, so a true many-to-many
+/// primitive would require vendoring and modifying RoutingKit itself rather than wrapping it.
+/// Treat `CCHMatrix` as `sources.len() * targets.len()` independent shortest-path queries with a
+/// convenient batch API, not as a performance primitive.
+///
+/// Three many-to-many engines cover overlapping shapes of this problem; pick by access pattern:
+/// use `CCHMatrix` for a one-shot table where sources and targets are both known up front, switch
+/// to [`CCHManyToMany`] when one side (usually targets) is fixed and reselected across many
+/// calls, and reach for [`CCHQuery::compute_matrix`] when sources vastly outnumber targets and the
+/// per-source queries should run in parallel.
+pub struct CCHMatrix<'a> {
+    inner: UniquePtr<ffi::CCHMatrix>,
+    metric: &'a CCHMetric<'a>,
+    _marker: std::marker::PhantomData<std::cell::Cell<()>>, // Not Sync
+}
+
+impl<'a> CCHMatrix<'a> {
+    /// Allocate a new distance-table engine bound to a customized `metric`.
+    pub fn new(metric: &'a CCHMetric<'a>) -> Self {
+        let inner = unsafe { cch_matrix_new(&metric.inner) };
+        CCHMatrix {
+            inner,
+            metric,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Compute the `sources.len() * targets.len()` row-major distance matrix.
+    /// `result[s * targets.len() + t]` is the distance from `sources[s]` to `targets[t]`, or
+    /// `u32::MAX` if unreachable.
+    pub fn compute(&mut self, sources: &[u32], targets: &[u32]) -> Vec<u32> {
+        unsafe { cch_matrix_compute(self.inner.as_mut().unwrap(), sources, targets) }
+    }
+}
+
+/// Incremental many-to-many distance table over a customized [`CCHMetric`].
+///
+/// Where [`CCHMatrix::compute`] takes both sources and targets in one call, `CCHManyToMany`
+/// selects them independently and caches each side across [`CCHManyToMany::run`] calls: reselecting
+/// one side (e.g. a growing source batch against a fixed target set for an isochrone) avoids
+/// re-passing the other side to the underlying engine. Like [`CCHMatrix`], `run` itself is one
+/// independent [`CCHQuery`] per (source, target) pair, not RoutingKit's native bucket-based
+/// many-to-many engine — see the note on [`CCHMatrix`] for why that amortized engine isn't
+/// something this crate can add as a wrapper over RoutingKit's public API. Reselecting a side only
+/// saves you re-passing it to this API; it does not amortize any query work across calls. Prefer
+/// [`CCHMatrix`] when both sides are known up front and there's no reselection to amortize, and
+/// [`CCHQuery::compute_matrix`] for batches dominated by a large, parallelizable source set against
+/// a small fixed target set.
+pub struct CCHManyToMany<'a> {
+    inner: UniquePtr<ffi::CCHManyToMany>,
+    _metric: &'a CCHMetric<'a>,
+    num_sources: usize,
+    num_targets: usize,
+    _marker: std::marker::PhantomData<std::cell::Cell<()>>, // Not Sync
+}
+
+impl<'a> CCHManyToMany<'a> {
+    /// Allocate a new incremental many-to-many engine bound to a customized `metric`.
+    pub fn new(metric: &'a CCHMetric<'a>) -> Self {
+        let inner = unsafe { cch_many_to_many_new(&metric.inner) };
+        CCHManyToMany {
+            inner,
+            _metric: metric,
+            num_sources: 0,
+            num_targets: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Select the source set for subsequent [`CCHManyToMany::run`] calls, replacing any previous
+    /// selection.
+    pub fn select_sources(&mut self, sources: &[u32]) {
+        unsafe { cch_many_to_many_select_sources(self.inner.as_mut().unwrap(), sources) }
+        self.num_sources = sources.len();
+    }
+
+    /// Select the target set for subsequent [`CCHManyToMany::run`] calls, replacing any previous
+    /// selection.
+    pub fn select_targets(&mut self, targets: &[u32]) {
+        unsafe { cch_many_to_many_select_targets(self.inner.as_mut().unwrap(), targets) }
+        self.num_targets = targets.len();
+    }
+
+    /// Compute the row-major `num_sources * num_targets` distance matrix for the currently
+    /// selected sources/targets (`u32::MAX` for unreachable pairs).
+    ///
+    /// Panics: if called before at least one [`CCHManyToMany::select_sources`] and
+    /// [`CCHManyToMany::select_targets`] call.
+    pub fn run(&mut self) -> Vec<u32> {
+        assert!(
+            self.num_sources > 0 && self.num_targets > 0,
+            "CCHManyToMany::run called before selecting both sources and targets"
+        );
+        unsafe { cch_many_to_many_run(self.inner.as_mut().unwrap()) }
+    }
 }
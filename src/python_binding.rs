@@ -1,11 +1,27 @@
 use crate::{
-    CCH, CCHMetric, CCHMetricPartialUpdater, CCHQuery, CCHQueryResult, compute_order_degree,
-    compute_order_inertial,
+    compute_order_degree, compute_order_inertial, CCHMetric, CCHMetricPartialUpdater, CCHQuery,
+    CCHQueryResult, Progress, CCH,
 };
+use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
+use std::cell::UnsafeCell;
 use std::collections::HashMap;
 use std::rc::Rc;
-use std::sync::Arc;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Reacquire the GIL to invoke an optional Python progress callback with `(phase, fraction)`.
+/// Errors raised by the callback can't propagate out of the `FnMut(Progress)` closure, so they're
+/// reported as unraisable rather than silently dropped.
+fn report_progress(callback: &Option<Py<PyAny>>, event: Progress) {
+    let Some(callback) = callback else {
+        return;
+    };
+    Python::attach(|py| {
+        if let Err(err) = callback.call1(py, (event.phase, event.fraction)) {
+            err.write_unraisable(py, None);
+        }
+    });
+}
 
 #[pyfunction]
 #[pyo3(name = "compute_order_degree")]
@@ -32,14 +48,47 @@ struct PyCCH(Arc<CCH>);
 #[pymethods]
 impl PyCCH {
     #[new]
-    fn new(order: Vec<u32>, tail: Vec<u32>, head: Vec<u32>, filter_always_inf_arcs: bool) -> Self {
-        Self(Arc::new(CCH::new(
-            &order,
-            &tail,
-            &head,
-            |_| {},
-            filter_always_inf_arcs,
-        )))
+    #[pyo3(signature = (order, tail, head, filter_always_inf_arcs, progress=None))]
+    fn new(
+        py: Python,
+        order: Vec<u32>,
+        tail: Vec<u32>,
+        head: Vec<u32>,
+        filter_always_inf_arcs: bool,
+        progress: Option<Py<PyAny>>,
+    ) -> Self {
+        let cch = py.detach(|| {
+            CCH::new_with_progress(&order, &tail, &head, filter_always_inf_arcs, |event| {
+                report_progress(&progress, event)
+            })
+        });
+        Self(Arc::new(cch))
+    }
+
+    /// Persist the inputs this `CCH` was built from (`order`/`tail`/`head`/
+    /// `filter_always_inf_arcs`, not the contracted hierarchy) to `path`, fingerprinted over those
+    /// inputs so a mismatched cache can never be silently reused.
+    fn save(&self, path: String) -> PyResult<()> {
+        self.0
+            .save(path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Reload a `CCH` previously written by `save`, skipping nested dissection; contraction still
+    /// reruns from the cached order. `order`/`tail`/`head`/`filter_always_inf_arcs` must be the
+    /// exact inputs the cache was built from; raises `ValueError` if the embedded fingerprint
+    /// doesn't match.
+    #[staticmethod]
+    fn load(
+        path: String,
+        order: Vec<u32>,
+        tail: Vec<u32>,
+        head: Vec<u32>,
+        filter_always_inf_arcs: bool,
+    ) -> PyResult<Self> {
+        let cch = CCH::load(path, &order, &tail, &head, filter_always_inf_arcs)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self(Arc::new(cch)))
     }
 }
 
@@ -53,11 +102,17 @@ struct PyCCHMetric {
 #[pymethods]
 impl PyCCHMetric {
     #[new]
-    fn new(cch: &PyCCH, weights: Vec<u32>) -> Self {
+    #[pyo3(signature = (cch, weights, progress=None))]
+    fn new(py: Python, cch: &PyCCH, weights: Vec<u32>, progress: Option<Py<PyAny>>) -> Self {
         let arc_cch = cch.0.clone();
         let cch_static = unsafe { &*Arc::as_ptr(&arc_cch) };
+        let metric = py.detach(|| {
+            CCHMetric::new_with_progress(cch_static, weights, |event| {
+                report_progress(&progress, event)
+            })
+        });
         Self {
-            inner: Arc::new(CCHMetric::new(cch_static, weights)),
+            inner: Arc::new(metric),
             _cch: arc_cch,
         }
     }
@@ -66,6 +121,27 @@ impl PyCCHMetric {
     fn weights(&self) -> Vec<u32> {
         self.inner.weights().to_vec()
     }
+
+    /// Persist the customized metric to `path`, headered with the owning `CCH`'s fingerprint.
+    fn save(&self, path: String) -> PyResult<()> {
+        self.inner
+            .save(path)
+            .map_err(|e| PyIOError::new_err(e.to_string()))
+    }
+
+    /// Reload a metric previously written by `save`, bound to `cch`. Raises `ValueError` if the
+    /// cache's embedded fingerprint doesn't match `cch` (it was built for a different hierarchy).
+    #[staticmethod]
+    fn load(cch: &PyCCH, path: String) -> PyResult<Self> {
+        let arc_cch = cch.0.clone();
+        let cch_static = unsafe { &*Arc::as_ptr(&arc_cch) };
+        let metric =
+            CCHMetric::load(cch_static, path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self {
+            inner: Arc::new(metric),
+            _cch: arc_cch,
+        })
+    }
 }
 
 #[pyclass(unsendable)]
@@ -87,12 +163,21 @@ impl PyCCHMetricPartialUpdater {
         }
     }
 
-    fn apply(&mut self, metric: &mut PyCCHMetric, updates: HashMap<u32, u32>) {
-        self.inner.apply(
-            Arc::get_mut(&mut metric.inner)
-                .expect("cannot update CCHMetric: multiple references exist"),
-            &updates,
-        );
+    #[pyo3(signature = (metric, updates, progress=None))]
+    fn apply(
+        &mut self,
+        py: Python,
+        metric: &mut PyCCHMetric,
+        updates: HashMap<u32, u32>,
+        progress: Option<Py<PyAny>>,
+    ) {
+        let metric = Arc::get_mut(&mut metric.inner)
+            .expect("cannot update CCHMetric: multiple references exist");
+        py.detach(|| {
+            self.inner.apply_with_progress(metric, &updates, |event| {
+                report_progress(&progress, event)
+            })
+        });
     }
 }
 
@@ -160,6 +245,12 @@ impl PyCCHQuery {
             _query: self.inner.clone(),
         }
     }
+
+    /// Full `sources x targets` distance table, computed in parallel across `sources` with the
+    /// GIL released. Each entry is one independent shortest-path query; this is not RPHAST.
+    fn compute_matrix(&self, py: Python, sources: Vec<u32>, targets: Vec<u32>) -> Vec<Vec<u32>> {
+        py.detach(|| self.inner.compute_matrix(&sources, &targets))
+    }
 }
 
 #[pyclass(unsendable)]
@@ -187,8 +278,171 @@ impl PyCCHQueryResult {
     }
 }
 
+/// A single idle [`CCHQuery`] handed out by [`PyCCHQueryPool::checkout`]. Returned to the pool's
+/// free list on drop, which is what lets `run`/`run_multi_st`/`compute_matrix` be called
+/// concurrently from several Python threads without the single-reference `assert!` that
+/// [`PyCCHQuery`] relies on.
+struct PyCCHQueryPoolWorkspace<'p> {
+    pool: &'p PyCCHQueryPool,
+    index: usize,
+}
+
+impl PyCCHQueryPoolWorkspace<'_> {
+    fn query(&self) -> &mut CCHQuery<'static> {
+        // Safety: `index` was popped off `pool.free` by `checkout`, so no other
+        // `PyCCHQueryPoolWorkspace` can be holding the same index until this one drops.
+        unsafe { &mut *self.pool.workspaces[self.index].get() }
+    }
+}
+
+impl Drop for PyCCHQueryPoolWorkspace<'_> {
+    fn drop(&mut self) {
+        self.pool.free.lock().unwrap().push(self.index);
+        self.pool.available.notify_one();
+    }
+}
+
+/// Thread-safe pool of pre-allocated [`CCHQuery`] workspaces sharing one [`CCHMetric`], for
+/// querying the same hierarchy concurrently from several Python threads.
+///
+/// Borrows the shared-reference discipline hg-cpython uses for handing out long-lived Rust
+/// references to Python: the metric is stored once (no per-call `Arc::as_ptr` cast) and each
+/// workspace is handed out as a checked borrow tied to the pool's lifetime, tracked by a free
+/// list rather than a raw pointer plus a runtime reference-count `assert!`. A workspace can only
+/// be checked out once at a time by construction, so `unsendable` is unnecessary here.
+#[pyclass]
+#[pyo3(name = "CCHQueryPool")]
+struct PyCCHQueryPool {
+    _metric: Arc<CCHMetric<'static>>,
+    workspaces: Vec<UnsafeCell<CCHQuery<'static>>>,
+    free: Mutex<Vec<usize>>,
+    available: Condvar,
+}
+
+// Safety: every `workspaces[i]` is only ever dereferenced by the `PyCCHQueryPoolWorkspace` that
+// popped `i` off `free`, and `free`/`available` make that checkout exclusive.
+unsafe impl Sync for PyCCHQueryPool {}
+
+impl PyCCHQueryPool {
+    fn checkout(&self) -> PyCCHQueryPoolWorkspace<'_> {
+        let mut free = self.free.lock().unwrap();
+        while free.is_empty() {
+            free = self.available.wait(free).unwrap();
+        }
+        let index = free.pop().expect("just checked free is non-empty");
+        PyCCHQueryPoolWorkspace { pool: self, index }
+    }
+}
+
+#[pymethods]
+impl PyCCHQueryPool {
+    /// Pre-allocate `workspace_count` reusable queries bound to `metric` (at least one).
+    #[new]
+    fn new(metric: &PyCCHMetric, workspace_count: usize) -> Self {
+        let workspace_count = workspace_count.max(1);
+        let metric_arc = metric.inner.clone();
+        let metric_static = unsafe { &*Arc::as_ptr(&metric_arc) };
+        let workspaces = (0..workspace_count)
+            .map(|_| UnsafeCell::new(CCHQuery::new(metric_static)))
+            .collect();
+        Self {
+            _metric: metric_arc,
+            workspaces,
+            free: Mutex::new((0..workspace_count).collect()),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Single-source/single-target query on an idle workspace, blocking (with the GIL released)
+    /// until one is free if every workspace is currently checked out.
+    fn run(&self, py: Python, source: u32, target: u32) -> PyCCHQueryPoolResult {
+        py.detach(|| {
+            let workspace = self.checkout();
+            let query = workspace.query();
+            query.reset();
+            query.add_source(source, 0);
+            query.add_target(target, 0);
+            query.run();
+            PyCCHQueryPoolResult {
+                distance: query.distance(),
+                node_path: query.node_path(),
+                arc_path: query.arc_path(),
+            }
+        })
+    }
+
+    /// Multi-source/multi-target query (each paired with an initial distance) on an idle
+    /// workspace.
+    fn run_multi_st(
+        &self,
+        py: Python,
+        sources: Vec<(u32, u32)>,
+        targets: Vec<(u32, u32)>,
+    ) -> PyCCHQueryPoolResult {
+        py.detach(|| {
+            let workspace = self.checkout();
+            let query = workspace.query();
+            query.reset();
+            for (s, d) in sources {
+                query.add_source(s, d);
+            }
+            for (t, d) in targets {
+                query.add_target(t, d);
+            }
+            query.run();
+            PyCCHQueryPoolResult {
+                distance: query.distance(),
+                node_path: query.node_path(),
+                arc_path: query.arc_path(),
+            }
+        })
+    }
+
+    /// Full `sources x targets` distance table on an idle workspace, one independent
+    /// shortest-path query per entry (not RPHAST).
+    fn compute_matrix(&self, py: Python, sources: Vec<u32>, targets: Vec<u32>) -> Vec<Vec<u32>> {
+        py.detach(|| {
+            let workspace = self.checkout();
+            workspace.query().compute_matrix(&sources, &targets)
+        })
+    }
+}
+
+/// Owned result of a [`PyCCHQueryPool`] query — unlike [`PyCCHQueryResult`], it doesn't borrow the
+/// workspace it came from, since that workspace may already be back in the pool and reused by the
+/// time Python reads these fields.
+#[pyclass(frozen)]
+#[pyo3(name = "CCHQueryPoolResult")]
+struct PyCCHQueryPoolResult {
+    distance: Option<u32>,
+    node_path: Vec<u32>,
+    arc_path: Vec<u32>,
+}
+
+#[pymethods]
+impl PyCCHQueryPoolResult {
+    #[getter]
+    fn distance(&self) -> Option<u32> {
+        self.distance
+    }
+
+    #[getter]
+    fn node_path(&self) -> Vec<u32> {
+        self.node_path.clone()
+    }
+
+    #[getter]
+    fn arc_path(&self) -> Vec<u32> {
+        self.arc_path.clone()
+    }
+}
+
 #[pymodule]
 mod routingkit_cch {
+    #[pymodule_export]
+    use super::py_compute_order_degree;
+    #[pymodule_export]
+    use super::py_compute_order_inertial;
     #[pymodule_export]
     use super::PyCCH;
     #[pymodule_export]
@@ -198,9 +452,9 @@ mod routingkit_cch {
     #[pymodule_export]
     use super::PyCCHQuery;
     #[pymodule_export]
-    use super::PyCCHQueryResult;
+    use super::PyCCHQueryPool;
     #[pymodule_export]
-    use super::py_compute_order_degree;
+    use super::PyCCHQueryPoolResult;
     #[pymodule_export]
-    use super::py_compute_order_inertial;
+    use super::PyCCHQueryResult;
 }
@@ -0,0 +1,85 @@
+//! Parallel batch query execution over a single shared [`CCHMetric`].
+//!
+//! [`CCHQuery`] is `Send` but not `Sync` (each query owns mutable frontier/label buffers), while
+//! [`CCHMetric`] is `Sync`. [`CCHQueryPool`] hides the thread plumbing that follows from that:
+//! one reusable `CCHQuery` per worker thread, sharded pairs, results gathered back in input order.
+
+use crate::{CCHMetric, CCHQuery};
+
+/// A fixed-size pool of worker threads sharing a single [`CCHMetric`], for running large batches
+/// of independent `(source, target)` queries in parallel.
+///
+/// The pool holds only the metric and the thread count. Each [`CCHQueryPool::distances`] /
+/// [`CCHQueryPool::paths`] call allocates one [`CCHQuery`] per worker thread and reuses it for
+/// every pair in that thread's shard; queries are not kept between calls.
+pub struct CCHQueryPool<'a> {
+    metric: &'a CCHMetric<'a>,
+    thread_count: usize,
+}
+
+impl<'a> CCHQueryPool<'a> {
+    /// Create a pool that shards batches across up to `thread_count` worker threads.
+    pub fn new(metric: &'a CCHMetric<'a>, thread_count: usize) -> Self {
+        CCHQueryPool {
+            metric,
+            thread_count: thread_count.max(1),
+        }
+    }
+
+    /// Shortest path distance for every `(source, target)` pair, in the same order as `pairs`.
+    /// `None` marks an unreachable pair.
+    pub fn distances(&self, pairs: &[(u32, u32)]) -> Vec<Option<u32>> {
+        self.run(pairs, |query, s, t| {
+            query.reset();
+            query.add_source(s, 0);
+            query.add_target(t, 0);
+            query.run();
+            query.distance()
+        })
+    }
+
+    /// Shortest path node sequence for every `(source, target)` pair, in the same order as
+    /// `pairs`. An unreachable pair yields an empty vec (see [`CCHQuery::node_path`]).
+    pub fn paths(&self, pairs: &[(u32, u32)]) -> Vec<Vec<u32>> {
+        self.run(pairs, |query, s, t| {
+            query.reset();
+            query.add_source(s, 0);
+            query.add_target(t, 0);
+            query.run();
+            query.node_path()
+        })
+    }
+
+    /// Shard `pairs` across `self.thread_count` worker threads, each running `op` against its
+    /// own reusable `CCHQuery`, and concatenate the per-thread results back in input order.
+    fn run<T: Send>(
+        &self,
+        pairs: &[(u32, u32)],
+        op: impl Fn(&mut CCHQuery<'a>, u32, u32) -> T + Sync,
+    ) -> Vec<T> {
+        if pairs.is_empty() {
+            return Vec::new();
+        }
+        let thread_count = self.thread_count.min(pairs.len());
+        let chunk_size = pairs.len().div_ceil(thread_count);
+        let metric = self.metric;
+        let op = &op;
+        std::thread::scope(|scope| {
+            pairs
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut query = CCHQuery::new(metric);
+                        chunk
+                            .iter()
+                            .map(|&(s, t)| op(&mut query, s, t))
+                            .collect::<Vec<T>>()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,486 @@
+//! Loading OSM-derived shapefiles (nodes/edges) into plain arrays suitable for [`crate::CCH`].
+
+use rstar::{PointDistance, RTree, RTreeObject, AABB};
+use shapefile::dbase::FieldValue;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct EdgeAttr {
+    pub fid: u64,
+    pub u: u64,
+    pub v: u64,
+    pub length: f64,
+    pub highway: Option<String>,
+    pub name: Option<String>,
+    pub oneway: Option<String>,
+    pub maxspeed: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeAttr {
+    pub osmid: u64,
+    pub x: f64,
+    pub y: f64,
+    pub highway: Option<String>,
+    pub r#ref: Option<String>,
+}
+
+pub struct GraphArrays {
+    pub osmids: Vec<u64>,
+    pub xs: Vec<f64>,
+    pub ys: Vec<f64>,
+    pub tail: Vec<usize>,
+    pub head: Vec<usize>,
+    pub weight: Vec<f64>,
+}
+
+impl std::fmt::Debug for GraphArrays {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const FRONT: usize = 5;
+        const BACK: usize = 5;
+        fn slice_fmt<T: std::fmt::Debug>(
+            f: &mut std::fmt::Formatter<'_>,
+            name: &str,
+            data: &[T],
+        ) -> std::fmt::Result {
+            let len = data.len();
+            if len <= FRONT + BACK {
+                write!(f, "\n{name}[len={len}] = {:?}", data)
+            } else {
+                let front = &data[..FRONT];
+                let back = &data[len - BACK..];
+                write!(
+                    f,
+                    "\n{name}[len={len}] front={:?} ... back={:?}",
+                    front, back
+                )
+            }
+        }
+        let (w_min, w_max, w_sum) = self
+            .weight
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY, 0f64), |acc, &w| {
+                (acc.0.min(w), acc.1.max(w), acc.2 + w)
+            });
+        let w_avg = if self.weight.is_empty() {
+            0.0
+        } else {
+            w_sum / self.weight.len() as f64
+        };
+        write!(
+            f,
+            "GraphArrays summary: nodes={} edges={}",
+            self.osmids.len(),
+            self.tail.len()
+        )?;
+        slice_fmt(f, "osmids", &self.osmids)?;
+        slice_fmt(f, "x", &self.xs)?;
+        slice_fmt(f, "y", &self.ys)?;
+        slice_fmt(f, "tail", &self.tail)?;
+        slice_fmt(f, "head", &self.head)?;
+        slice_fmt(f, "weight", &self.weight)?;
+        write!(
+            f,
+            "\nweight_stats: min={:.3} max={:.3} avg={:.3}",
+            w_min, w_max, w_avg
+        )?;
+        Ok(())
+    }
+}
+
+trait RecordExt {
+    fn fv(&self, key: &str) -> Option<&FieldValue>;
+    fn num(&self, key: &str) -> Option<u64>;
+    fn f64v(&self, key: &str) -> Option<f64>;
+    fn strv(&self, key: &str) -> Option<String>;
+    fn must_num(&self, key: &str, idx: usize, kind: &str) -> Result<u64, String>;
+    fn must_f64(&self, key: &str, idx: usize, kind: &str) -> Result<f64, String>;
+}
+impl RecordExt for shapefile::dbase::Record {
+    fn fv(&self, key: &str) -> Option<&FieldValue> {
+        self.get(key)
+    }
+    fn num(&self, key: &str) -> Option<u64> {
+        self.fv(key).and_then(|v| match v {
+            FieldValue::Numeric(opt) => opt.map(|f| f as u64),
+            FieldValue::Character(Some(s)) => s.parse().ok(),
+            _ => None,
+        })
+    }
+    fn f64v(&self, key: &str) -> Option<f64> {
+        self.fv(key).and_then(|v| match v {
+            FieldValue::Numeric(opt) => opt.map(|f| f as f64),
+            FieldValue::Character(Some(s)) => s.parse().ok(),
+            _ => None,
+        })
+    }
+    fn strv(&self, key: &str) -> Option<String> {
+        self.fv(key).and_then(|v| match v {
+            FieldValue::Character(Some(s)) => Some(s.trim().to_string()),
+            _ => None,
+        })
+    }
+    fn must_num(&self, key: &str, idx: usize, kind: &str) -> Result<u64, String> {
+        self.num(key)
+            .ok_or_else(|| format!("Missing required field '{key}' at {kind} record {idx}"))
+    }
+    fn must_f64(&self, key: &str, idx: usize, kind: &str) -> Result<f64, String> {
+        self.f64v(key)
+            .ok_or_else(|| format!("Missing required field '{key}' at {kind} record {idx}"))
+    }
+}
+
+pub fn load_edges<P: AsRef<Path>>(path: &P) -> Result<Vec<EdgeAttr>, Box<dyn std::error::Error>> {
+    let mut reader = shapefile::Reader::from_path(path)?;
+    let mut edges = Vec::new();
+    let mut idx = 0usize;
+    for rec in reader.iter_shapes_and_records() {
+        let (_shape, record) = rec?;
+        let fid = record.must_num("fid", idx, "edge")?;
+        let u = record.must_num("u", idx, "edge")?;
+        let v = record.must_num("v", idx, "edge")?;
+        let length = record.must_f64("length", idx, "edge")?;
+        edges.push(EdgeAttr {
+            fid,
+            u,
+            v,
+            length,
+            highway: record.strv("highway"),
+            name: record.strv("name"),
+            oneway: record.strv("oneway"),
+            maxspeed: record.strv("maxspeed"),
+        });
+        idx += 1;
+    }
+    Ok(edges)
+}
+
+pub fn load_nodes<P: AsRef<Path>>(path: &P) -> Result<Vec<NodeAttr>, Box<dyn std::error::Error>> {
+    let mut reader = shapefile::Reader::from_path(path)?;
+    let mut nodes = Vec::new();
+    let mut idx = 0usize;
+    for rec in reader.iter_shapes_and_records() {
+        let (shape, record) = rec?;
+        let (x, y) = match shape {
+            shapefile::Shape::Point(p) => (p.x, p.y),
+            shapefile::Shape::PointZ(p) => (p.x, p.y),
+            _ => continue,
+        };
+        let osmid = record.must_num("osmid", idx, "node")?;
+        let highway = record.strv("highway");
+        let r#ref = record.strv("ref");
+        nodes.push(NodeAttr {
+            osmid,
+            x,
+            y,
+            highway,
+            r#ref,
+        });
+        idx += 1;
+    }
+    Ok(nodes)
+}
+
+pub fn build_graph_arrays(nodes: &[NodeAttr], edges: &[EdgeAttr]) -> Result<GraphArrays, String> {
+    use std::collections::HashMap;
+    let mut id_map = HashMap::with_capacity(nodes.len());
+    let mut osmids = Vec::with_capacity(nodes.len());
+    let mut xs = Vec::with_capacity(nodes.len());
+    let mut ys = Vec::with_capacity(nodes.len());
+    for (i, n) in nodes.iter().enumerate() {
+        if id_map.insert(n.osmid, i).is_some() {
+            return Err(format!("Duplicate osmid {}", n.osmid));
+        }
+        osmids.push(n.osmid);
+        xs.push(n.x);
+        ys.push(n.y);
+    }
+    let mut tail = Vec::with_capacity(edges.len());
+    let mut head = Vec::with_capacity(edges.len());
+    let mut weight = Vec::with_capacity(edges.len());
+    for e in edges {
+        let &tu = id_map
+            .get(&e.u)
+            .ok_or_else(|| format!("Edge u osmid {} not found", e.u))?;
+        let &hv = id_map
+            .get(&e.v)
+            .ok_or_else(|| format!("Edge v osmid {} not found", e.v))?;
+        tail.push(tu);
+        head.push(hv);
+        weight.push(e.length);
+    }
+    Ok(GraphArrays {
+        osmids,
+        xs,
+        ys,
+        tail,
+        head,
+        weight,
+    })
+}
+
+/// Per-`highway`-class default speeds (km/h) and `maxspeed` parsing, used to convert raw edge
+/// length into a travel-time weight.
+///
+/// Speeds are free-flow defaults loosely matching OSM's `highway` tag taxonomy; override any of
+/// them (or the fallback) to match a particular region or driving profile.
+#[derive(Debug, Clone)]
+pub struct WeightProfile {
+    /// `highway` tag value (e.g. `"motorway"`, `"residential"`) -> default speed in km/h.
+    pub highway_speeds_kmh: std::collections::HashMap<String, f64>,
+    /// Speed used when `highway` is missing or not in `highway_speeds_kmh`.
+    pub default_speed_kmh: f64,
+}
+
+impl Default for WeightProfile {
+    /// A generic car profile with conservative free-flow speeds per OSM `highway` class.
+    fn default() -> Self {
+        let pairs: &[(&str, f64)] = &[
+            ("motorway", 100.0),
+            ("motorway_link", 60.0),
+            ("trunk", 85.0),
+            ("trunk_link", 50.0),
+            ("primary", 65.0),
+            ("primary_link", 45.0),
+            ("secondary", 55.0),
+            ("secondary_link", 40.0),
+            ("tertiary", 45.0),
+            ("tertiary_link", 35.0),
+            ("unclassified", 40.0),
+            ("residential", 30.0),
+            ("living_street", 15.0),
+            ("service", 15.0),
+            ("track", 20.0),
+        ];
+        WeightProfile {
+            highway_speeds_kmh: pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+            default_speed_kmh: 30.0,
+        }
+    }
+}
+
+impl WeightProfile {
+    /// Speed (km/h) to use for an edge with the given `highway` tag, falling back to
+    /// [`WeightProfile::default_speed_kmh`] for unknown/missing classes.
+    pub fn highway_speed_kmh(&self, highway: Option<&str>) -> f64 {
+        highway
+            .and_then(|h| self.highway_speeds_kmh.get(h))
+            .copied()
+            .unwrap_or(self.default_speed_kmh)
+    }
+
+    /// Parse an OSM-style `maxspeed` tag into km/h.
+    ///
+    /// Handles plain numbers (`"30"`, assumed km/h), explicit units (`"30 mph"`), and
+    /// `;`-separated lists (the first parseable entry wins, as OSM lists fastest-first by
+    /// convention). Returns `None` for missing/unparseable/`"none"` values (e.g. derestricted
+    /// Autobahn sections), leaving the caller to fall back to the highway-class default.
+    pub fn parse_maxspeed_kmh(maxspeed: Option<&str>) -> Option<f64> {
+        let raw = maxspeed?.trim();
+        if raw.is_empty() || raw.eq_ignore_ascii_case("none") || raw.eq_ignore_ascii_case("signals")
+        {
+            return None;
+        }
+        for part in raw.split(';') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            if let Some(mph) = part
+                .strip_suffix("mph")
+                .or_else(|| part.strip_suffix("mph."))
+            {
+                if let Ok(v) = mph.trim().parse::<f64>() {
+                    return Some(v * 1.609344);
+                }
+                continue;
+            }
+            let numeric = part.trim_end_matches("km/h").trim_end_matches("kmh").trim();
+            if let Ok(v) = numeric.parse::<f64>() {
+                return Some(v);
+            }
+        }
+        None
+    }
+
+    /// Resolved speed (km/h) for an edge: the parsed `maxspeed` if present, else the
+    /// `highway`-class default.
+    pub fn speed_kmh(&self, highway: Option<&str>, maxspeed: Option<&str>) -> f64 {
+        Self::parse_maxspeed_kmh(maxspeed).unwrap_or_else(|| self.highway_speed_kmh(highway))
+    }
+}
+
+/// Does `oneway` mean "no reverse arc"? OSM commonly uses `"yes"`/`"1"`/`"true"`; anything else
+/// (including missing) is treated as two-way.
+fn is_oneway(oneway: Option<&str>) -> bool {
+    matches!(
+        oneway.map(|s| s.trim().to_ascii_lowercase()).as_deref(),
+        Some("yes") | Some("true") | Some("1")
+    )
+}
+
+/// Like [`build_graph_arrays`], but weights each arc by travel time (milliseconds) instead of
+/// raw length, using `profile` to turn `maxspeed`/`highway` into a speed.
+///
+/// Two-way edges (`oneway` absent or falsy) get both a forward and a reverse arc at the same
+/// weight; `oneway` edges contribute only the forward arc `u -> v`.
+pub fn build_graph_arrays_with_profile(
+    nodes: &[NodeAttr],
+    edges: &[EdgeAttr],
+    profile: &WeightProfile,
+) -> Result<GraphArrays, String> {
+    use std::collections::HashMap;
+    let mut id_map = HashMap::with_capacity(nodes.len());
+    let mut osmids = Vec::with_capacity(nodes.len());
+    let mut xs = Vec::with_capacity(nodes.len());
+    let mut ys = Vec::with_capacity(nodes.len());
+    for (i, n) in nodes.iter().enumerate() {
+        if id_map.insert(n.osmid, i).is_some() {
+            return Err(format!("Duplicate osmid {}", n.osmid));
+        }
+        osmids.push(n.osmid);
+        xs.push(n.x);
+        ys.push(n.y);
+    }
+    let mut tail = Vec::with_capacity(edges.len());
+    let mut head = Vec::with_capacity(edges.len());
+    let mut weight = Vec::with_capacity(edges.len());
+    for e in edges {
+        let &tu = id_map
+            .get(&e.u)
+            .ok_or_else(|| format!("Edge u osmid {} not found", e.u))?;
+        let &hv = id_map
+            .get(&e.v)
+            .ok_or_else(|| format!("Edge v osmid {} not found", e.v))?;
+        let speed_kmh = profile.speed_kmh(e.highway.as_deref(), e.maxspeed.as_deref());
+        let time_ms = (e.length / (speed_kmh * 1000.0 / 3600.0) * 1000.0).round();
+        tail.push(tu);
+        head.push(hv);
+        weight.push(time_ms);
+        if !is_oneway(e.oneway.as_deref()) {
+            tail.push(hv);
+            head.push(tu);
+            weight.push(time_ms);
+        }
+    }
+    Ok(GraphArrays {
+        osmids,
+        xs,
+        ys,
+        tail,
+        head,
+        weight,
+    })
+}
+
+/// Mean radius of the Earth in meters, used for haversine distance.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+fn haversine_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lon1, lat2, lon2) = (
+        lat1.to_radians(),
+        lon1.to_radians(),
+        lat2.to_radians(),
+        lon2.to_radians(),
+    );
+    let dlat = lat2 - lat1;
+    let dlon = lon2 - lon1;
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * a.sqrt().asin()
+}
+
+/// A single indexed point: `(lon, lat)` paired with the `GraphArrays` node index it came from.
+struct IndexedNode {
+    lon: f64,
+    lat: f64,
+    node_idx: u32,
+}
+
+impl RTreeObject for IndexedNode {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point([self.lon, self.lat])
+    }
+}
+
+impl PointDistance for IndexedNode {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        // Equirectangular approximation scaled to meters: cheap enough to drive the tree's
+        // nearest-neighbour and range search, with haversine used afterwards for exact meters.
+        let lat_scale = self.lat.to_radians().cos().max(0.01);
+        let dx = (self.lon - point[0]) * lat_scale;
+        let dy = self.lat - point[1];
+        let meters_per_degree = EARTH_RADIUS_M.to_radians();
+        let dx_m = dx * meters_per_degree;
+        let dy_m = dy * meters_per_degree;
+        dx_m * dx_m + dy_m * dy_m
+    }
+}
+
+/// Spatial index over a [`GraphArrays`] node set, for snapping raw GPS coordinates to the
+/// nearest graph node id.
+///
+/// Built once from `xs`/`ys` (lon/lat); the returned indices are the *post-remap* node ids
+/// produced by [`build_graph_arrays`], so they plug directly into [`crate::CCHQuery::add_source`]
+/// / [`crate::CCHQuery::add_target`].
+pub struct NodeLocator {
+    tree: RTree<IndexedNode>,
+}
+
+impl NodeLocator {
+    /// Build a locator from parallel `xs` (longitude) / `ys` (latitude) vectors, such as
+    /// `GraphArrays::xs` / `GraphArrays::ys`.
+    pub fn new(xs: &[f64], ys: &[f64]) -> Self {
+        assert_eq!(xs.len(), ys.len(), "xs and ys must have the same length");
+        let points = xs
+            .iter()
+            .zip(ys.iter())
+            .enumerate()
+            .map(|(i, (&lon, &lat))| IndexedNode {
+                lon,
+                lat,
+                node_idx: i as u32,
+            })
+            .collect();
+        NodeLocator {
+            tree: RTree::bulk_load(points),
+        }
+    }
+
+    pub fn from_graph_arrays(graph: &GraphArrays) -> Self {
+        Self::new(&graph.xs, &graph.ys)
+    }
+
+    /// Return the node index nearest to `(lat, lon)`, or `None` if the index is empty.
+    ///
+    /// Deliberately `Option<u32>` rather than a bare `u32`: unlike `add_source`/`add_target`,
+    /// which take a node id the caller already knows exists, a `NodeLocator` can be built from an
+    /// empty node set, in which case there is no nearest node to return.
+    pub fn nearest(&self, lat: f64, lon: f64) -> Option<u32> {
+        self.tree.nearest_neighbor(&[lon, lat]).map(|n| n.node_idx)
+    }
+
+    /// Return every node index within `meters` of `(lat, lon)`, nearest first.
+    ///
+    /// The R-tree range query uses the cheap equirectangular envelope above as a coarse filter;
+    /// results are then re-checked and sorted by true haversine distance so the radius is honored
+    /// in real-world meters rather than raw degrees.
+    pub fn within_radius(&self, lat: f64, lon: f64, meters: f64) -> Vec<u32> {
+        let lat_scale = lat.to_radians().cos().max(0.01);
+        let meters_per_degree = EARTH_RADIUS_M.to_radians();
+        let dlat = meters / meters_per_degree;
+        let dlon = meters / (meters_per_degree * lat_scale);
+        let envelope = AABB::from_corners([lon - dlon, lat - dlat], [lon + dlon, lat + dlat]);
+        let mut candidates: Vec<(f64, u32)> = self
+            .tree
+            .locate_in_envelope(&envelope)
+            .filter_map(|n| {
+                let d = haversine_m(lat, lon, n.lat, n.lon);
+                (d <= meters).then_some((d, n.node_idx))
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+        candidates.into_iter().map(|(_, idx)| idx).collect()
+    }
+}
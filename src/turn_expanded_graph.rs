@@ -0,0 +1,105 @@
+//! Edge-expanded graph construction for turn-cost / turn-restriction routing.
+//!
+//! [`crate::CCH`] only understands a plain node/arc graph, so turn penalties and banned turns
+//! have to be modeled by expanding each original arc into a node of a new graph, with an arc
+//! between two expanded nodes for every (incoming-arc, node, outgoing-arc) turn that's allowed.
+//! The resulting `tail`/`head`/`weights` can be fed straight into [`crate::CCH::new`] and
+//! [`crate::CCHMetric::new`]; `latitude`/`longitude` (one midpoint per original arc) can be fed
+//! into [`crate::compute_order_inertial`].
+
+use std::collections::HashMap;
+
+/// An edge-expanded graph built from an original node/arc graph plus a turn-cost table.
+///
+/// Expanded nodes are original arc ids 1:1 (expanded node `i` *is* original arc `i`), so no
+/// separate node mapping table is needed; [`TurnExpandedGraph::original_arc_path`] translates a
+/// path back using that identity.
+pub struct TurnExpandedGraph {
+    /// Expanded-graph arc tails (incoming original arc ids).
+    pub tail: Box<[u32]>,
+    /// Expanded-graph arc heads (outgoing original arc ids).
+    pub head: Box<[u32]>,
+    /// Per expanded arc, the turn cost looked up in the `turn_costs` table (0 if unlisted).
+    pub weights: Box<[u32]>,
+    /// Per expanded node (original arc), the latitude of that arc's midpoint.
+    pub latitude: Box<[f32]>,
+    /// Per expanded node (original arc), the longitude of that arc's midpoint.
+    pub longitude: Box<[f32]>,
+}
+
+impl TurnExpandedGraph {
+    /// Build the expanded graph from the original `tail`/`head` arcs, the original nodes'
+    /// `node_lat`/`node_lon` (length = node count), and a turn-cost table keyed by
+    /// `(incoming_arc, node, outgoing_arc)`.
+    ///
+    /// A turn missing from `turn_costs` is free (cost 0); a turn costed at `u32::MAX` is banned
+    /// and its expanded arc is omitted entirely.
+    pub fn build(
+        tail: &[u32],
+        head: &[u32],
+        node_lat: &[f32],
+        node_lon: &[f32],
+        turn_costs: &HashMap<(u32, u32, u32), u32>,
+    ) -> Self {
+        let node_count = node_lat.len();
+        let mut out_arcs_by_node: Vec<Vec<u32>> = vec![Vec::new(); node_count];
+        for (arc, &t) in tail.iter().enumerate() {
+            out_arcs_by_node[t as usize].push(arc as u32);
+        }
+
+        let mut expanded_tail = Vec::new();
+        let mut expanded_head = Vec::new();
+        let mut expanded_weights = Vec::new();
+        for (in_arc, &via_node) in head.iter().enumerate() {
+            for &out_arc in &out_arcs_by_node[via_node as usize] {
+                let cost = turn_costs
+                    .get(&(in_arc as u32, via_node, out_arc))
+                    .copied()
+                    .unwrap_or(0);
+                if cost == u32::MAX {
+                    continue;
+                }
+                expanded_tail.push(in_arc as u32);
+                expanded_head.push(out_arc);
+                expanded_weights.push(cost);
+            }
+        }
+
+        let midpoint = |arc: usize| -> (f32, f32) {
+            let (a, b) = (tail[arc] as usize, head[arc] as usize);
+            (
+                (node_lat[a] + node_lat[b]) / 2.0,
+                (node_lon[a] + node_lon[b]) / 2.0,
+            )
+        };
+        let mut latitude = Vec::with_capacity(tail.len());
+        let mut longitude = Vec::with_capacity(tail.len());
+        for arc in 0..tail.len() {
+            let (lat, lon) = midpoint(arc);
+            latitude.push(lat);
+            longitude.push(lon);
+        }
+
+        TurnExpandedGraph {
+            tail: expanded_tail.into(),
+            head: expanded_head.into(),
+            weights: expanded_weights.into(),
+            latitude: latitude.into(),
+            longitude: longitude.into(),
+        }
+    }
+
+    /// Translate a path of expanded-graph arc ids (as returned by
+    /// [`crate::CCHQuery::arc_path`] run against a `CCH` built from this graph) back into the
+    /// sequence of original arc ids it visits.
+    pub fn original_arc_path(&self, expanded_arc_path: &[u32]) -> Vec<u32> {
+        let mut path = Vec::with_capacity(expanded_arc_path.len() + 1);
+        if let Some(&first) = expanded_arc_path.first() {
+            path.push(self.tail[first as usize]);
+        }
+        for &arc in expanded_arc_path {
+            path.push(self.head[arc as usize]);
+        }
+        path
+    }
+}
@@ -0,0 +1,217 @@
+//! Optimal (or near-optimal) visit ordering for a small set of waypoints, built on top of
+//! [`crate::CCHMatrix`].
+
+use crate::{CCHMatrix, CCHMetric};
+
+/// Sentinel cost [`crate::CCHMatrix`] uses for an unreachable pair.
+const UNREACHABLE: u32 = u32::MAX;
+
+/// Exact cutoff above which [`solve_waypoint_order`] falls back to a 2-opt local search instead
+/// of Held–Karp (`O(n^2 * 2^n)` becomes too expensive past this).
+const EXACT_LIMIT: usize = 10;
+
+/// Return the node-visit permutation (as node ids, same domain as `waypoints`) minimizing total
+/// CCH distance through all of `waypoints`.
+///
+/// * `fix_start` – if `true`, the route must start at `waypoints[0]`; otherwise every waypoint is
+///   tried as a start and the best overall is kept.
+/// * `fix_end` – if `true`, the route must end at `waypoints[waypoints.len() - 1]` (an open path);
+///   otherwise the route closes back to its start (a round trip).
+///
+/// For up to [`EXACT_LIMIT`] waypoints this runs an exact Held–Karp dynamic program; beyond that
+/// it seeds with the input order and improves it with 2-opt, which is no longer guaranteed
+/// optimal.
+///
+/// Returns an empty vec if `waypoints` is empty, and `waypoints.to_vec()` unchanged if there is
+/// only one.
+pub fn solve_waypoint_order(
+    metric: &CCHMetric,
+    waypoints: &[u32],
+    fix_start: bool,
+    fix_end: bool,
+) -> Vec<u32> {
+    let n = waypoints.len();
+    if n <= 1 {
+        return waypoints.to_vec();
+    }
+
+    let dist = distance_matrix(metric, waypoints);
+
+    let order = if n <= EXACT_LIMIT {
+        held_karp_best(&dist, fix_start, fix_end).0
+    } else {
+        two_opt(&dist, fix_start, fix_end)
+    };
+
+    order.into_iter().map(|i| waypoints[i]).collect()
+}
+
+/// Build the dense `n x n` distance matrix among `waypoints` via one [`CCHMatrix`] call.
+fn distance_matrix(metric: &CCHMetric, waypoints: &[u32]) -> Vec<Vec<u32>> {
+    let n = waypoints.len();
+    let mut matrix = CCHMatrix::new(metric);
+    let flat = matrix.compute(waypoints, waypoints);
+    (0..n).map(|i| flat[i * n..(i + 1) * n].to_vec()).collect()
+}
+
+/// Try every allowed start (just `0` if `fix_start`) and keep the globally cheapest Held–Karp
+/// solution. Returns (visit order as indices into `dist`, total cost).
+fn held_karp_best(dist: &[Vec<u32>], fix_start: bool, fix_end: bool) -> (Vec<usize>, u64) {
+    let n = dist.len();
+    let starts: Vec<usize> = if fix_start { vec![0] } else { (0..n).collect() };
+    let required_end = if fix_end { Some(n - 1) } else { None };
+
+    starts
+        .into_iter()
+        .filter(|&s| required_end != Some(s) || n == 1)
+        .map(|s| held_karp(dist, s, required_end))
+        .min_by_key(|(_, cost)| *cost)
+        .unwrap_or_else(|| ((0..n).collect(), 0))
+}
+
+/// Exact Held–Karp dynamic program.
+///
+/// `dp[mask][j]` = minimum cost of a path starting at `start`, visiting exactly the waypoint set
+/// `mask` (which always includes `start` and `j`), and ending at `j`. Subsets are iterated in
+/// increasing numeric order of `mask` via the outer loop, which reaches every strict subset of a
+/// mask before the mask itself (all that `dp[mask]` depends on), with the recurrence
+/// `dp[mask | {k}][k] = min over j in mask of dp[mask][j] + dist[j][k]`.
+///
+/// If `required_end` is `Some(t)`, the tour is an open path that must finish at `t`. Otherwise it
+/// closes back to `start` (a round trip), and the cheapest closing edge picks the free end.
+fn held_karp(dist: &[Vec<u32>], start: usize, required_end: Option<usize>) -> (Vec<usize>, u64) {
+    let n = dist.len();
+    let full = 1usize << n;
+    const INF: u64 = u64::MAX / 2;
+    let cost = |a: usize, b: usize| -> u64 {
+        if dist[a][b] == UNREACHABLE {
+            INF
+        } else {
+            dist[a][b] as u64
+        }
+    };
+
+    let mut dp = vec![vec![INF; n]; full];
+    let mut parent = vec![vec![usize::MAX; n]; full];
+    dp[1 << start][start] = 0;
+
+    for mask in 0..full {
+        if mask & (1 << start) == 0 {
+            continue;
+        }
+        for j in 0..n {
+            if mask & (1 << j) == 0 || dp[mask][j] == INF {
+                continue;
+            }
+            let base = dp[mask][j];
+            for k in 0..n {
+                if mask & (1 << k) != 0 {
+                    continue;
+                }
+                let next_mask = mask | (1 << k);
+                let candidate = base + cost(j, k);
+                if candidate < dp[next_mask][k] {
+                    dp[next_mask][k] = candidate;
+                    parent[next_mask][k] = j;
+                }
+            }
+        }
+    }
+
+    let full_mask = full - 1;
+    let (end, total) = match required_end {
+        Some(t) => (t, dp[full_mask][t]),
+        None => (0..n)
+            .map(|j| (j, dp[full_mask][j].saturating_add(cost(j, start))))
+            .min_by_key(|&(_, c)| c)
+            .unwrap(),
+    };
+
+    // Reconstruct the visit order by walking parent pointers back from `end`.
+    let mut order = Vec::with_capacity(n);
+    let mut mask = full_mask;
+    let mut node = end;
+    loop {
+        order.push(node);
+        let prev = parent[mask][node];
+        if prev == usize::MAX {
+            break;
+        }
+        mask &= !(1 << node);
+        node = prev;
+    }
+    order.reverse();
+    // If every full path needs an unreachable leg, the parent walk stops short of `n` nodes; fall
+    // back to `start`, the other waypoints in index order, then `required_end`, at cost `INF`.
+    if order.len() < n {
+        let order = std::iter::once(start)
+            .chain((0..n).filter(|&i| i != start && Some(i) != required_end))
+            .chain(required_end)
+            .collect();
+        return (order, INF);
+    }
+    (order, total)
+}
+
+/// 2-opt local search seeded by the input order, for instances too large for exact Held–Karp.
+/// Not guaranteed optimal, but removes the obvious edge crossings.
+///
+/// For a round trip (`fix_end == false`) the wraparound edge `order[n - 1] -> order[0]` closes
+/// the tour and is costed like any other edge when a candidate move touches either end.
+fn two_opt(dist: &[Vec<u32>], fix_start: bool, fix_end: bool) -> Vec<usize> {
+    let n = dist.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    let cost = |a: usize, b: usize| -> u64 {
+        if dist[a][b] == UNREACHABLE {
+            u64::MAX / 2
+        } else {
+            dist[a][b] as u64
+        }
+    };
+    // A round trip closes back to its start, so the wraparound edge order[n-1] -> order[0] is
+    // part of the tour like any other and must be costed when a move touches either end.
+    let round_trip = !fix_end;
+    // Indices that 2-opt is allowed to move: keep position 0 (resp. n-1) pinned when fixed.
+    let lo = if fix_start { 1 } else { 0 };
+    let hi = if fix_end { n - 1 } else { n };
+
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in lo..hi.saturating_sub(1) {
+            for j in (i + 1)..hi {
+                // Reversing the entire tour (i == 0 && j == n - 1) just walks the same cycle in
+                // the other direction: the wraparound edge moves with it but costs the same
+                // either way, so there is no move to evaluate here.
+                if round_trip && i == 0 && j == n - 1 {
+                    continue;
+                }
+                let prev = if i == 0 {
+                    if round_trip {
+                        Some(order[n - 1])
+                    } else {
+                        None
+                    }
+                } else {
+                    Some(order[i - 1])
+                };
+                let next_after_j = if j + 1 < n {
+                    Some(order[j + 1])
+                } else if round_trip {
+                    Some(order[0])
+                } else {
+                    None
+                };
+                let before = prev.map(|p| cost(p, order[i])).unwrap_or(0)
+                    + next_after_j.map(|q| cost(order[j], q)).unwrap_or(0);
+                let after = prev.map(|p| cost(p, order[j])).unwrap_or(0)
+                    + next_after_j.map(|q| cost(order[i], q)).unwrap_or(0);
+                if after < before {
+                    order[i..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+    order
+}
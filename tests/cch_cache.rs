@@ -0,0 +1,66 @@
+use routingkit_cch::{compute_order_degree, CchCacheError, CCH};
+
+#[test]
+fn save_then_load_roundtrips_fingerprint() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+
+    let path = std::env::temp_dir().join(format!("cch_cache_test_{}.bin", std::process::id()));
+    cch.save(&path).unwrap();
+
+    let reloaded = CCH::load(&path, &order, &tail, &head, false).unwrap();
+    assert_eq!(reloaded.fingerprint(), cch.fingerprint());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn load_rejects_mismatched_topology() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+
+    let path = std::env::temp_dir().join(format!(
+        "cch_cache_test_mismatch_{}.bin",
+        std::process::id()
+    ));
+    cch.save(&path).unwrap();
+
+    // Same shape, different weights-independent topology (extra arc) -> different fingerprint.
+    let other_tail = vec![0u32, 1, 0, 2];
+    let other_head = vec![1u32, 2, 2, 0];
+    let other_order = compute_order_degree(3, &other_tail, &other_head);
+    let result = CCH::load(&path, &other_order, &other_tail, &other_head, false);
+    assert!(matches!(result, Err(CchCacheError::FingerprintMismatch)));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn load_rejects_a_truncated_payload_instead_of_aborting() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+
+    let path = std::env::temp_dir().join(format!(
+        "cch_cache_test_truncated_{}.bin",
+        std::process::id()
+    ));
+    cch.save(&path).unwrap();
+
+    // Keep the magic + fingerprint header intact (so it passes those checks) but cut the payload
+    // off partway through the last length-prefixed array (`head`, plus the trailing flag byte), so
+    // cch_deserialize hits end-of-buffer mid-read.
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.truncate(bytes.len() - 8);
+    std::fs::write(&path, &bytes).unwrap();
+
+    let result = CCH::load(&path, &order, &tail, &head, false);
+    assert!(matches!(result, Err(CchCacheError::Corrupt(_))));
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,29 @@
+use routingkit_cch::{compute_order_degree, InvalidOrderError, CCH};
+
+#[test]
+fn from_order_accepts_a_genuine_permutation_and_order_reads_it_back() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let order = compute_order_degree(3, &tail, &head);
+
+    let cch = CCH::from_order(&order, &tail, &head, false).unwrap();
+    assert_eq!(cch.order(), order.as_slice());
+}
+
+#[test]
+fn from_order_rejects_duplicates_and_out_of_range_ids() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+
+    let duplicate = vec![0u32, 0, 2];
+    assert!(matches!(
+        CCH::from_order(&duplicate, &tail, &head, false),
+        Err(InvalidOrderError::Duplicate(0))
+    ));
+
+    let out_of_range = vec![0u32, 1, 3];
+    assert!(matches!(
+        CCH::from_order(&out_of_range, &tail, &head, false),
+        Err(InvalidOrderError::OutOfRange(3))
+    ));
+}
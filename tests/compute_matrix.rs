@@ -0,0 +1,38 @@
+use routingkit_cch::{compute_order_degree, CCHMetric, CCH};
+
+#[test]
+fn compute_matrix_matches_independent_phast_to_targets_rows() {
+    let tail = vec![0u32, 1, 2, 0];
+    let head = vec![1u32, 2, 3, 2];
+    let weights = vec![1u32, 2, 4, 10];
+    let order = compute_order_degree(4, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+    let query = routingkit_cch::CCHQuery::new(&metric);
+
+    let sources = vec![0u32, 1, 3];
+    let targets = vec![2u32, 3];
+    let matrix = query.compute_matrix(&sources, &targets);
+
+    let expected: Vec<Vec<u32>> = sources
+        .iter()
+        .map(|&s| query.phast_to_targets(s, &targets))
+        .collect();
+    assert_eq!(matrix, expected);
+}
+
+#[test]
+fn compute_matrix_reports_unreachable_pairs_as_u32_max() {
+    // Node 2 has no path to node 0: compute_matrix must report u32::MAX, not RoutingKit's raw
+    // i32::MAX sentinel.
+    let tail = vec![0u32, 1];
+    let head = vec![1u32, 2];
+    let weights = vec![5u32, 7];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+    let query = routingkit_cch::CCHQuery::new(&metric);
+
+    let matrix = query.compute_matrix(&[2], &[0]);
+    assert_eq!(matrix, vec![vec![u32::MAX]]);
+}
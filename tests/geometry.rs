@@ -0,0 +1,75 @@
+use routingkit_cch::shp_utils::{build_graph_arrays, EdgeAttr, NodeAttr};
+use routingkit_cch::PathGeometry;
+
+fn tiny_graph() -> routingkit_cch::shp_utils::GraphArrays {
+    let nodes = vec![
+        NodeAttr {
+            osmid: 1,
+            x: -120.2,
+            y: 38.5,
+            highway: None,
+            r#ref: None,
+        },
+        NodeAttr {
+            osmid: 2,
+            x: -120.95,
+            y: 40.7,
+            highway: None,
+            r#ref: None,
+        },
+        NodeAttr {
+            osmid: 3,
+            x: -126.453,
+            y: 43.252,
+            highway: None,
+            r#ref: None,
+        },
+    ];
+    let edges = vec![
+        EdgeAttr {
+            fid: 0,
+            u: 1,
+            v: 2,
+            length: 1.0,
+            highway: None,
+            name: None,
+            oneway: None,
+            maxspeed: None,
+        },
+        EdgeAttr {
+            fid: 1,
+            u: 2,
+            v: 3,
+            length: 1.0,
+            highway: None,
+            name: None,
+            oneway: None,
+            maxspeed: None,
+        },
+    ];
+    build_graph_arrays(&nodes, &edges).unwrap()
+}
+
+#[test]
+fn encodes_known_polyline() {
+    // The canonical Google polyline algorithm example: these three points encode to
+    // "_p~iF~ps|U_ulLnnqC_mqNvxq`@".
+    let graph = tiny_graph();
+    let geometry = PathGeometry::new(&graph, &[0, 1, 2]);
+    assert_eq!(geometry.to_polyline(), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+}
+
+#[test]
+fn geojson_round_trips_coordinates_and_properties() {
+    let graph = tiny_graph();
+    let geometry = PathGeometry::new(&graph, &[0, 1])
+        .with_distance_m(1234.5)
+        .with_eta_seconds(60.0);
+    let value = geometry.to_geojson();
+    assert_eq!(value["type"], "Feature");
+    assert_eq!(value["geometry"]["type"], "LineString");
+    assert_eq!(value["geometry"]["coordinates"][0][0], -120.2);
+    assert_eq!(value["geometry"]["coordinates"][0][1], 38.5);
+    assert_eq!(value["properties"]["distance_m"], 1234.5);
+    assert_eq!(value["properties"]["eta_seconds"], 60.0);
+}
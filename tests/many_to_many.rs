@@ -0,0 +1,71 @@
+use routingkit_cch::{compute_order_degree, CCHManyToMany, CCHMatrix, CCHMetric, CCH};
+
+#[test]
+fn many_to_many_matches_single_pair_queries() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let weights = vec![5u32, 7, 20];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let mut engine = CCHManyToMany::new(&metric);
+    engine.select_sources(&[0, 1]);
+    engine.select_targets(&[2]);
+    let result = engine.run();
+    assert_eq!(result, vec![12, 7]);
+
+    // Same answer via the one-shot CCHMatrix.
+    let mut matrix = CCHMatrix::new(&metric);
+    assert_eq!(matrix.compute(&[0, 1], &[2]), vec![12, 7]);
+}
+
+#[test]
+fn matrix_reports_unreachable_pairs_as_u32_max() {
+    // Node 2 has no path to node 0: the matrix must report u32::MAX, not RoutingKit's raw
+    // i32::MAX sentinel.
+    let tail = vec![0u32, 1];
+    let head = vec![1u32, 2];
+    let weights = vec![5u32, 7];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let mut matrix = CCHMatrix::new(&metric);
+    assert_eq!(matrix.compute(&[2], &[0]), vec![u32::MAX]);
+}
+
+#[test]
+fn reselecting_targets_reruns_independently_of_sources() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let weights = vec![5u32, 7, 20];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let mut engine = CCHManyToMany::new(&metric);
+    engine.select_sources(&[0]);
+    engine.select_targets(&[1]);
+    assert_eq!(engine.run(), vec![5]);
+
+    engine.select_targets(&[2]);
+    assert_eq!(engine.run(), vec![12]);
+}
+
+#[test]
+fn many_to_many_reports_unreachable_pairs_as_u32_max() {
+    // Node 2 has no path to node 0: CCHManyToMany must report u32::MAX, not RoutingKit's raw
+    // i32::MAX sentinel.
+    let tail = vec![0u32, 1];
+    let head = vec![1u32, 2];
+    let weights = vec![5u32, 7];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let mut engine = CCHManyToMany::new(&metric);
+    engine.select_sources(&[2]);
+    engine.select_targets(&[0]);
+    assert_eq!(engine.run(), vec![u32::MAX]);
+}
@@ -0,0 +1,72 @@
+use routingkit_cch::{compute_order_degree, CCHMetric, CchCacheError, CCH};
+
+#[test]
+fn save_then_load_roundtrips_weights() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let weights = vec![5u32, 7, 20];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights.clone());
+
+    let path = std::env::temp_dir().join(format!("metric_cache_test_{}.bin", std::process::id()));
+    metric.save(&path).unwrap();
+
+    let reloaded = CCHMetric::load(&cch, &path).unwrap();
+    assert_eq!(reloaded.weights(), weights.as_slice());
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn load_rejects_cache_bound_to_a_different_cch() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let weights = vec![5u32, 7, 20];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let path = std::env::temp_dir().join(format!(
+        "metric_cache_test_mismatch_{}.bin",
+        std::process::id()
+    ));
+    metric.save(&path).unwrap();
+
+    let other_tail = vec![0u32, 1, 0, 2];
+    let other_head = vec![1u32, 2, 2, 0];
+    let other_order = compute_order_degree(3, &other_tail, &other_head);
+    let other_cch = CCH::new(&other_order, &other_tail, &other_head, false);
+    let result = CCHMetric::load(&other_cch, &path);
+    assert!(matches!(result, Err(CchCacheError::FingerprintMismatch)));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn load_rejects_a_truncated_payload_instead_of_aborting() {
+    let tail = vec![0u32, 1, 0];
+    let head = vec![1u32, 2, 2];
+    let weights = vec![5u32, 7, 20];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let path = std::env::temp_dir().join(format!(
+        "metric_cache_test_truncated_{}.bin",
+        std::process::id()
+    ));
+    metric.save(&path).unwrap();
+
+    // Keep the magic + fingerprint header intact (so it passes those checks) but cut the weight
+    // payload off partway through, so load() must reject the cache instead of reading past the
+    // end of the buffer.
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    std::fs::write(&path, &bytes).unwrap();
+
+    let result = CCHMetric::load(&cch, &path);
+    assert!(matches!(result, Err(CchCacheError::Corrupt(_))));
+
+    std::fs::remove_file(&path).ok();
+}
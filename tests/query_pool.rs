@@ -0,0 +1,33 @@
+use routingkit_cch::{compute_order_degree, CCHMetric, CCHQueryPool, CCH};
+
+#[test]
+fn distances_preserve_input_order_across_threads() {
+    let tail = vec![0u32, 1, 2];
+    let head = vec![1u32, 2, 3];
+    let weights = vec![1u32, 2, 4];
+    let order = compute_order_degree(4, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let pool = CCHQueryPool::new(&metric, 4);
+    let pairs = vec![(0u32, 3u32), (1, 2), (3, 0), (0, 1)];
+    let distances = pool.distances(&pairs);
+
+    assert_eq!(distances, vec![Some(7), Some(2), None, Some(1)]);
+}
+
+#[test]
+fn paths_match_sequential_single_threaded_queries() {
+    let tail = vec![0u32, 1, 2];
+    let head = vec![1u32, 2, 3];
+    let weights = vec![1u32, 2, 4];
+    let order = compute_order_degree(4, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let pool = CCHQueryPool::new(&metric, 2);
+    let pairs = vec![(0u32, 2u32), (1, 3)];
+    let paths = pool.paths(&pairs);
+
+    assert_eq!(paths, vec![vec![0, 1, 2], vec![1, 2, 3]]);
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use routingkit_cch::shp_utils::NodeLocator;
 use shapefile::dbase::FieldValue;
 use std::path::Path;
 
@@ -314,3 +315,31 @@ fn test_load_paths() {
         "max edge id in paths exceeds total edges"
     );
 }
+
+#[test]
+fn nearest_returns_none_for_an_empty_index() {
+    let locator = NodeLocator::new(&[], &[]);
+    assert_eq!(locator.nearest(39.9, 116.4), None);
+}
+
+#[test]
+fn nearest_and_within_radius_find_the_closest_points() {
+    // Three nodes roughly 0, 111m and 222m north of the origin (1 degree of latitude is
+    // approximately 111km).
+    let xs = vec![0.0, 0.0, 0.0];
+    let ys = vec![0.0, 0.001, 0.002];
+    let locator = NodeLocator::new(&xs, &ys);
+
+    assert_eq!(locator.nearest(0.0, 0.0), Some(0));
+    assert_eq!(locator.nearest(0.0011, 0.0), Some(1));
+
+    let within = locator.within_radius(0.0, 0.0, 150.0);
+    assert_eq!(within, vec![0, 1]);
+
+    let all = locator.within_radius(0.0, 0.0, 1000.0);
+    assert_eq!(all, vec![0, 1, 2]);
+
+    // Node 0 sits exactly on the query point, so even a 1m radius still contains it.
+    let only_origin = locator.within_radius(0.0, 0.0, 1.0);
+    assert_eq!(only_origin, vec![0]);
+}
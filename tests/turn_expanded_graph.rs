@@ -0,0 +1,52 @@
+use routingkit_cch::TurnExpandedGraph;
+use std::collections::HashMap;
+
+#[test]
+fn builds_turn_edges_between_arcs_sharing_a_node() {
+    // 0 --arc0--> 1 --arc1--> 2, and 1 --arc2--> 3: arc0->arc1 and arc0->arc2 are the only turns.
+    let tail = vec![0u32, 1, 1];
+    let head = vec![1u32, 2, 3];
+    let node_lat = vec![0.0f32, 1.0, 2.0, 1.0];
+    let node_lon = vec![0.0f32, 0.0, 0.0, 1.0];
+
+    let turn_costs = HashMap::new();
+    let expanded = TurnExpandedGraph::build(&tail, &head, &node_lat, &node_lon, &turn_costs);
+
+    assert_eq!(expanded.tail.as_ref(), &[0u32, 0]);
+    assert_eq!(expanded.head.as_ref(), &[1u32, 2]);
+    assert_eq!(expanded.weights.as_ref(), &[0u32, 0]);
+    // arc0's midpoint is the average of node 0 and node 1.
+    assert_eq!(expanded.latitude[0], 0.5);
+    assert_eq!(expanded.longitude[0], 0.0);
+}
+
+#[test]
+fn banned_turn_is_omitted_and_costed_turn_keeps_its_weight() {
+    let tail = vec![0u32, 1, 1];
+    let head = vec![1u32, 2, 3];
+    let node_lat = vec![0.0f32, 1.0, 2.0, 1.0];
+    let node_lon = vec![0.0f32, 0.0, 0.0, 1.0];
+
+    let mut turn_costs = HashMap::new();
+    turn_costs.insert((0u32, 1u32, 1u32), 5u32); // arc0 -> arc1 costs 5
+    turn_costs.insert((0u32, 1u32, 2u32), u32::MAX); // arc0 -> arc2 banned
+
+    let expanded = TurnExpandedGraph::build(&tail, &head, &node_lat, &node_lon, &turn_costs);
+    assert_eq!(expanded.tail.as_ref(), &[0u32]);
+    assert_eq!(expanded.head.as_ref(), &[1u32]);
+    assert_eq!(expanded.weights.as_ref(), &[5u32]);
+}
+
+#[test]
+fn original_arc_path_translates_expanded_arc_ids() {
+    let tail = vec![0u32, 1, 1];
+    let head = vec![1u32, 2, 3];
+    let node_lat = vec![0.0f32, 1.0, 2.0, 1.0];
+    let node_lon = vec![0.0f32, 0.0, 0.0, 1.0];
+    let turn_costs = HashMap::new();
+    let expanded = TurnExpandedGraph::build(&tail, &head, &node_lat, &node_lon, &turn_costs);
+
+    // Traversing expanded arc 0 (arc0 -> arc1) visits original arcs [0, 1].
+    assert_eq!(expanded.original_arc_path(&[0]), vec![0u32, 1]);
+    assert_eq!(expanded.original_arc_path(&[]), Vec::<u32>::new());
+}
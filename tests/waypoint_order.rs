@@ -0,0 +1,118 @@
+use routingkit_cch::{compute_order_degree, solve_waypoint_order, CCHMatrix, CCHMetric, CCH};
+
+#[test]
+fn orders_waypoints_by_total_cch_distance() {
+    // Square-ish graph: 0 -> 1 -> 2 -> 3 -> 0, all weight 1, plus shortcuts across the diagonal.
+    let tail = vec![0, 1, 2, 3, 0, 2];
+    let head = vec![1, 2, 3, 0, 2, 0];
+    let weights = vec![1u32, 1, 1, 1, 1, 1];
+    let order = compute_order_degree(4, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    // Visiting 1 then 3 starting at 0 and ending at 3 should prefer the cheap 0->1 (1) and
+    // 1->2->3 (2) path over any detour, i.e. 0,1,3 order with 2 left out entirely since it's not
+    // a requested waypoint.
+    let waypoints = vec![0u32, 1, 3];
+    let order = solve_waypoint_order(&metric, &waypoints, true, true);
+    assert_eq!(order.first(), Some(&0));
+    assert_eq!(order.last(), Some(&3));
+    assert_eq!(order.len(), 3);
+}
+
+#[test]
+fn single_and_empty_waypoints_are_noops() {
+    let tail = vec![0u32, 1];
+    let head = vec![1u32, 2];
+    let weights = vec![1u32, 1];
+    let order = compute_order_degree(3, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    assert_eq!(
+        solve_waypoint_order(&metric, &[], true, true),
+        Vec::<u32>::new()
+    );
+    assert_eq!(solve_waypoint_order(&metric, &[2], true, true), vec![2]);
+}
+
+#[test]
+fn unreachable_pairs_do_not_become_a_merely_expensive_detour() {
+    // {0,1} and {2,3} are two fully disconnected bidirectional components: any tour visiting
+    // both must cross the gap, so the distance matrix solve_waypoint_order works from must mark
+    // those pairs UNREACHABLE (u32::MAX) rather than RoutingKit's raw i32::MAX sentinel --
+    // otherwise Held-Karp would treat the gap as a merely-expensive ~2.1 billion-cost edge instead
+    // of excluding it.
+    let tail = vec![0u32, 1, 2, 3];
+    let head = vec![1u32, 0, 3, 2];
+    let weights = vec![1u32, 1, 1, 1];
+    let order = compute_order_degree(4, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    let mut matrix = CCHMatrix::new(&metric);
+    assert_eq!(matrix.compute(&[0], &[2]), vec![u32::MAX]);
+
+    // solve_waypoint_order has no way to report "impossible": it must still return a valid
+    // permutation of all requested waypoints instead of panicking or overflowing its u64 cost
+    // math on the unreachable legs.
+    let waypoints = vec![0u32, 1, 2, 3];
+    let result = solve_waypoint_order(&metric, &waypoints, true, true);
+    let mut sorted_result = result.clone();
+    sorted_result.sort();
+    assert_eq!(sorted_result, waypoints);
+}
+
+#[test]
+fn round_trip_two_opt_does_not_worsen_the_closing_edge() {
+    // An 11-node ring (bidirectional, weight 1 per hop) so there are more waypoints than
+    // `EXACT_LIMIT`, forcing the 2-opt fallback instead of exact Held-Karp.
+    const N: u32 = 11;
+    let mut tail = Vec::new();
+    let mut head = Vec::new();
+    let mut weights = Vec::new();
+    for i in 0..N {
+        let j = (i + 1) % N;
+        tail.push(i);
+        head.push(j);
+        weights.push(1u32);
+        tail.push(j);
+        head.push(i);
+        weights.push(1u32);
+    }
+    let order = compute_order_degree(N, &tail, &head);
+    let cch = CCH::new(&order, &tail, &head, false);
+    let metric = CCHMetric::new(&cch, weights);
+
+    // Deliberately out of order, including at both ends of the array, so 2-opt has to consider
+    // moves that touch the round-trip's wraparound edge.
+    let waypoints: Vec<u32> = vec![6, 1, 0, 9, 3, 4, 5, 2, 7, 8, 10];
+    assert!(waypoints.len() > 10, "must exceed EXACT_LIMIT to hit 2-opt");
+
+    let mut matrix = CCHMatrix::new(&metric);
+    let flat = matrix.compute(&waypoints, &waypoints);
+    let n = waypoints.len();
+    let index_of = |node: u32| waypoints.iter().position(|&w| w == node).unwrap();
+    let round_trip_cost = |order: &[u32]| -> u64 {
+        (0..order.len())
+            .map(|k| {
+                let a = index_of(order[k]);
+                let b = index_of(order[(k + 1) % order.len()]);
+                flat[a * n + b] as u64
+            })
+            .sum()
+    };
+
+    let result = solve_waypoint_order(&metric, &waypoints, false, false);
+    let mut sorted_result = result.clone();
+    sorted_result.sort();
+    let mut sorted_waypoints = waypoints.clone();
+    sorted_waypoints.sort();
+    assert_eq!(sorted_result, sorted_waypoints);
+
+    assert!(
+        round_trip_cost(&result) <= round_trip_cost(&waypoints),
+        "2-opt must not return a round trip costing more than the seed order, \
+         including the wraparound edge back to the start"
+    );
+}
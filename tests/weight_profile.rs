@@ -0,0 +1,74 @@
+use routingkit_cch::shp_utils::{
+    build_graph_arrays_with_profile, EdgeAttr, NodeAttr, WeightProfile,
+};
+
+#[test]
+fn parse_maxspeed_variants() {
+    assert_eq!(WeightProfile::parse_maxspeed_kmh(Some("30")), Some(30.0));
+    assert_eq!(
+        WeightProfile::parse_maxspeed_kmh(Some("30 mph")),
+        Some(30.0 * 1.609344)
+    );
+    assert_eq!(WeightProfile::parse_maxspeed_kmh(Some("50;60")), Some(50.0));
+    assert_eq!(WeightProfile::parse_maxspeed_kmh(Some("none")), None);
+    assert_eq!(WeightProfile::parse_maxspeed_kmh(None), None);
+}
+
+#[test]
+fn falls_back_to_highway_default_speed() {
+    let profile = WeightProfile::default();
+    assert_eq!(profile.speed_kmh(Some("residential"), None), 30.0);
+    assert_eq!(
+        profile.speed_kmh(Some("unknown_tag"), None),
+        profile.default_speed_kmh
+    );
+    assert_eq!(profile.speed_kmh(Some("residential"), Some("50")), 50.0);
+}
+
+#[test]
+fn oneway_skips_reverse_arc() {
+    let nodes = vec![
+        NodeAttr {
+            osmid: 1,
+            x: 0.0,
+            y: 0.0,
+            highway: None,
+            r#ref: None,
+        },
+        NodeAttr {
+            osmid: 2,
+            x: 0.0,
+            y: 0.0,
+            highway: None,
+            r#ref: None,
+        },
+    ];
+    let edges = vec![
+        EdgeAttr {
+            fid: 0,
+            u: 1,
+            v: 2,
+            length: 1000.0,
+            highway: Some("residential".to_string()),
+            name: None,
+            oneway: Some("yes".to_string()),
+            maxspeed: None,
+        },
+        EdgeAttr {
+            fid: 1,
+            u: 2,
+            v: 1,
+            length: 1000.0,
+            highway: Some("residential".to_string()),
+            name: None,
+            oneway: None,
+            maxspeed: None,
+        },
+    ];
+    let profile = WeightProfile::default();
+    let graph = build_graph_arrays_with_profile(&nodes, &edges, &profile).unwrap();
+    // edge 0 is oneway -> 1 arc; edge 1 is two-way -> 2 arcs
+    assert_eq!(graph.tail.len(), 3);
+    assert_eq!(graph.tail, vec![0, 1, 0]);
+    assert_eq!(graph.head, vec![1, 0, 1]);
+}